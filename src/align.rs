@@ -0,0 +1,258 @@
+use crate::Stream;
+
+/// How a time is matched to a sample by [`Stream::align_to`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Lookup {
+    /// The sample whose timestamp is closest to the time.
+    Nearest,
+    /// The last sample at or before the time.
+    Previous,
+}
+
+// All lookups assume the samples are in order of their timestamps, which is the case after parsing.
+// Samples without a timestamp are skipped.
+impl Stream {
+    // the position after the last timestamped sample for which `before` holds, found with a binary search.
+    // A sample without a timestamp is decided by the next sample which has one, so untimed samples only cost
+    // a linear scan over themselves. After parsing, every sample of a regular stream has a timestamp.
+    pub(crate) fn partition_by(&self, before: impl Fn(f64) -> bool) -> usize {
+        let (mut low, mut high) = (0, self.samples.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.samples[middle..high]
+                .iter()
+                .enumerate()
+                .find_map(|(i, s)| Some((middle + i, s.timestamp?)))
+            {
+                Some((i, ts)) if before(ts) => low = i + 1,
+                _ => high = middle,
+            }
+        }
+        low
+    }
+
+    // the index of the last sample with a timestamp before `partition`
+    pub(crate) fn timestamped_before(&self, partition: usize) -> Option<usize> {
+        self.samples[..partition].iter().rposition(|s| s.timestamp.is_some())
+    }
+
+    // the index of the first sample with a timestamp at or after `partition`
+    pub(crate) fn timestamped_from(&self, partition: usize) -> Option<usize> {
+        self.samples[partition..]
+            .iter()
+            .position(|s| s.timestamp.is_some())
+            .map(|i| i + partition)
+    }
+
+    /// Returns the index of the last sample with a timestamp at or before `time`, using a binary search.
+    /// None if all samples are later than `time`.
+    #[must_use]
+    pub fn previous_index(&self, time: f64) -> Option<usize> {
+        self.timestamped_before(self.partition_by(|ts| ts <= time))
+    }
+
+    /// Returns the index of the sample whose timestamp is closest to `time`, using a binary search.
+    /// If two samples are equally close, the earlier one is returned. None if no sample has a timestamp.
+    #[must_use]
+    pub fn nearest_index(&self, time: f64) -> Option<usize> {
+        let partition = self.partition_by(|ts| ts <= time);
+        let distance = |i: usize| self.samples[i].timestamp.map_or(f64::INFINITY, |ts| (ts - time).abs());
+
+        match (self.timestamped_before(partition), self.timestamped_from(partition)) {
+            (Some(previous), Some(next)) => Some(if distance(next) < distance(previous) {
+                next
+            } else {
+                previous
+            }),
+            (previous, next) => previous.or(next),
+        }
+    }
+
+    /**
+    Returns the values of a numeric stream at `time`, interpolated linearly between the neighbouring samples.
+
+    Returns None for string streams and for times outside of the range of the stream's timestamps.
+    A sample exactly at `time` is returned as is.
+    */
+    #[must_use]
+    #[allow(clippy::float_cmp)] // an exact match needs no interpolation, and there might not be a next sample
+    pub fn value_at(&self, time: f64) -> Option<Vec<f64>> {
+        let partition = self.partition_by(|ts| ts <= time);
+
+        let previous_sample = &self.samples[self.timestamped_before(partition)?];
+        let previous_values = previous_sample.values.to_f64_vec()?;
+        let previous_time = previous_sample.timestamp?;
+
+        if previous_time == time {
+            return Some(previous_values);
+        }
+
+        let next_sample = &self.samples[self.timestamped_from(partition)?];
+        let next_values = next_sample.values.to_f64_vec()?;
+        let next_time = next_sample.timestamp?;
+
+        let fraction = (time - previous_time) / (next_time - previous_time);
+        Some(
+            previous_values
+                .iter()
+                .zip(next_values)
+                .map(|(a, b)| a * (1.0 - fraction) + b * fraction)
+                .collect(),
+        )
+    }
+
+    /**
+    Maps each sample of this stream onto a sample of `target`, for example to find the EEG sample for each marker.
+
+    # Returns
+    A vector with one entry per sample of this stream, containing the index of the matching sample in `target`.
+    The entry is None if this stream's sample has no timestamp or no sample of `target` matches.
+    */
+    #[must_use]
+    pub fn align_to(&self, target: &Self, lookup: Lookup) -> Vec<Option<usize>> {
+        self.samples
+            .iter()
+            .map(|s| {
+                s.timestamp.and_then(|ts| match lookup {
+                    Lookup::Nearest => target.nearest_index(ts),
+                    Lookup::Previous => target.previous_index(ts),
+                })
+            })
+            .collect()
+    }
+
+    /**
+    Interpolates the values of this numeric stream at the timestamps of `target`'s samples,
+    for example to get the eye tracker's gaze position at every EEG sample. See [`Stream::value_at`].
+
+    # Returns
+    A vector with one entry per sample of `target`.
+    */
+    #[must_use]
+    pub fn interpolate_onto(&self, target: &Self) -> Vec<Option<Vec<f64>>> {
+        target
+            .samples
+            .iter()
+            .map(|s| s.timestamp.and_then(|ts| self.value_at(ts)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Format, Values};
+
+    const EPSILON: f64 = 1E-12;
+
+    fn eeg_stream() -> Stream {
+        // 10 Hz from 1.0 to 1.9 with the sample index as value
+        let samples = (0..10).map(|i| (1.0 + f64::from(i) / 10.0, Values::Int32(vec![i, -i])));
+        Stream::from_test_timestamps(Format::Int32, 2, Some(10.0), samples)
+    }
+
+    fn marker_stream(timestamps: &[Option<f64>]) -> Stream {
+        let samples = timestamps
+            .iter()
+            .map(|&timestamp| (timestamp, Values::String(vec!["marker".to_string()])));
+        Stream::from_test_timestamps(Format::String, 1, None, samples)
+    }
+
+    #[test]
+    fn test_index_lookups() {
+        let stream = eeg_stream();
+
+        assert_eq!(stream.previous_index(0.5), None);
+        assert_eq!(stream.previous_index(1.0), Some(0));
+        assert_eq!(stream.previous_index(1.34), Some(3));
+        assert_eq!(stream.previous_index(5.0), Some(9));
+
+        assert_eq!(stream.nearest_index(0.5), Some(0));
+        assert_eq!(stream.nearest_index(1.34), Some(3));
+        assert_eq!(stream.nearest_index(1.36), Some(4));
+        assert_eq!(stream.nearest_index(5.0), Some(9));
+
+        assert_eq!(marker_stream(&[None]).nearest_index(1.0), None);
+    }
+
+    #[test]
+    fn test_lookups_skip_missing_timestamps() {
+        let markers = marker_stream(&[Some(1.0), None, Some(2.0)]);
+
+        assert_eq!(markers.previous_index(0.5), None);
+        assert_eq!(markers.previous_index(1.5), Some(0));
+        assert_eq!(markers.nearest_index(1.6), Some(2));
+    }
+
+    #[test]
+    fn test_lookups_interleaved_missing_timestamps() {
+        let markers = marker_stream(&[Some(1.0), None, Some(2.0), None, None, Some(3.0), None]);
+
+        assert_eq!(markers.previous_index(0.5), None);
+        assert_eq!(markers.previous_index(1.0), Some(0));
+        assert_eq!(markers.previous_index(2.5), Some(2));
+        assert_eq!(markers.previous_index(4.0), Some(5));
+
+        assert_eq!(markers.nearest_index(0.5), Some(0));
+        assert_eq!(markers.nearest_index(2.6), Some(5));
+        assert_eq!(markers.nearest_index(4.0), Some(5));
+
+        let eeg = eeg_stream();
+        assert_eq!(
+            markers.align_to(&eeg, Lookup::Previous),
+            vec![Some(0), None, Some(9), None, None, Some(9), None]
+        );
+
+        let mut eeg_with_gaps = eeg_stream();
+        eeg_with_gaps.samples[0].timestamp = None;
+        eeg_with_gaps.samples[3].timestamp = None;
+        assert_eq!(eeg_with_gaps.previous_index(1.05), None);
+        assert_eq!(eeg_with_gaps.previous_index(1.35), Some(2));
+        assert_eq!(eeg_with_gaps.nearest_index(1.31), Some(4));
+
+        let values = eeg_with_gaps.value_at(1.3).unwrap();
+        assert!((values[0] - 3.0).abs() < EPSILON);
+        assert_eq!(eeg_with_gaps.value_at(1.05), None);
+    }
+
+    #[test]
+    fn test_value_at() {
+        let stream = eeg_stream();
+
+        let values = stream.value_at(1.25).unwrap();
+        assert!((values[0] - 2.5).abs() < EPSILON);
+        assert!((values[1] + 2.5).abs() < EPSILON);
+
+        assert_eq!(stream.value_at(1.0), Some(vec![0.0, 0.0]));
+        assert_eq!(stream.value_at(0.9), None);
+        assert_eq!(stream.value_at(2.0), None);
+
+        assert_eq!(marker_stream(&[Some(1.0), Some(2.0)]).value_at(1.5), None);
+    }
+
+    #[test]
+    fn test_align_to() {
+        let eeg = eeg_stream();
+        let markers = marker_stream(&[Some(0.5), Some(1.26), None, Some(1.9)]);
+
+        assert_eq!(
+            markers.align_to(&eeg, Lookup::Nearest),
+            vec![Some(0), Some(3), None, Some(9)]
+        );
+        assert_eq!(
+            markers.align_to(&eeg, Lookup::Previous),
+            vec![None, Some(2), None, Some(9)]
+        );
+    }
+
+    #[test]
+    fn test_interpolate_onto() {
+        let eeg = eeg_stream();
+        let markers = marker_stream(&[Some(0.5), Some(1.25)]);
+
+        let values = eeg.interpolate_onto(&markers);
+        assert_eq!(values[0], None);
+        assert!((values[1].as_ref().unwrap()[0] - 2.5).abs() < EPSILON);
+    }
+}
@@ -0,0 +1,176 @@
+//! Conversions between streams and [`ndarray`] arrays, available with the `ndarray` feature.
+
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+use xmltree::Element;
+
+use crate::{
+    errors::StreamError, measured_srate, util::set_text_of_child, ClockModel, Sample, Stream, StreamInfo, XdfScalar,
+};
+
+impl Stream {
+    /**
+    Copies the values of a numeric stream into an array of shape (samples × channels),
+    together with an array of the samples' timestamps. Missing timestamps are NaN.
+
+    # Errors
+    * [`StreamError::WrongFormat`] if the stream's format does not correspond to `T`.
+    * [`StreamError::MismatchedValues`] if the values of a sample do not match the stream's format or channel count.
+
+    # Example
+    ```rust
+    # use std::fs;
+    # use xdf::XDFFile;
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = fs::read("tests/minimal.xdf")?;
+    let xdf_file = XDFFile::from_bytes(&bytes)?;
+    let stream = xdf_file.streams.iter().find(|s| s.id == 0).unwrap();
+
+    let (data, timestamps) = stream.to_ndarray::<i16>()?;
+    assert_eq!(data.shape(), &[9, 3]);
+    assert_eq!(timestamps.len(), 9);
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn to_ndarray<T: XdfScalar>(&self) -> Result<(Array2<T>, Array1<f64>), StreamError> {
+        let rows = self.data::<T>()?;
+        let channel_count = self.channel_count as usize;
+
+        let mut values = Vec::with_capacity(rows.len() * channel_count);
+        for row in &rows {
+            if row.len() != channel_count {
+                return Err(StreamError::MismatchedValues(self.format));
+            }
+            values.extend_from_slice(row);
+        }
+
+        let data = Array2::from_shape_vec((rows.len(), channel_count), values)
+            .map_err(|_| StreamError::MismatchedValues(self.format))?;
+        let timestamps = self.samples.iter().map(|s| s.timestamp.unwrap_or(f64::NAN)).collect();
+
+        Ok((data, timestamps))
+    }
+
+    /**
+    Builds a stream from an array of shape (samples × channels) and the samples' timestamps,
+    for example after processing the output of [`Stream::to_ndarray`]. NaN timestamps become None.
+
+    The stream gets the id 0 and a minimal header containing the channel count, nominal sampling rate and format.
+    All other metadata is left empty and can be filled in afterwards.
+
+    # Errors
+    * [`StreamError::MismatchedTimestamps`] if the number of timestamps differs from the number of rows.
+    * [`StreamError::InvalidSrate`] if `nominal_srate` is not a positive, finite number.
+    */
+    pub fn from_ndarray<T: XdfScalar>(
+        data: ArrayView2<'_, T>,
+        timestamps: ArrayView1<'_, f64>,
+        nominal_srate: Option<f64>,
+    ) -> Result<Self, StreamError> {
+        if data.nrows() != timestamps.len() {
+            return Err(StreamError::MismatchedTimestamps {
+                samples: data.nrows(),
+                timestamps: timestamps.len(),
+            });
+        }
+        if let Some(srate) = nominal_srate.filter(|srate| !(*srate > 0.0 && srate.is_finite())) {
+            return Err(StreamError::InvalidSrate(srate));
+        }
+        let channel_count = u32::try_from(data.ncols()).map_err(|_| StreamError::MismatchedValues(T::FORMAT))?;
+
+        let samples: Vec<Sample> = data
+            .rows()
+            .into_iter()
+            .zip(timestamps)
+            .map(|(row, &timestamp)| Sample {
+                timestamp: (!timestamp.is_nan()).then_some(timestamp),
+                values: T::into_values(row.to_vec()),
+            })
+            .collect();
+
+        let recorded_timestamps = samples
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.timestamp.map(|_| i))
+            .collect();
+
+        let mut header = Element::new("info");
+        set_text_of_child(&mut header, "channel_count", &channel_count.to_string());
+        set_text_of_child(&mut header, "nominal_srate", &nominal_srate.unwrap_or(0.0).to_string());
+        set_text_of_child(&mut header, "channel_format", T::FORMAT.as_xdf_str());
+
+        Ok(Self {
+            id: 0,
+            channel_count,
+            nominal_srate,
+            format: T::FORMAT,
+            name: None,
+            r#type: None,
+            info: StreamInfo::default(),
+            header,
+            footer: None,
+            footer_info: None,
+            channels: Vec::new(),
+            measured_srate: measured_srate(&samples, nominal_srate),
+            samples,
+            recorded_timestamps,
+            clock_offsets: Vec::new(),
+            clock_model: ClockModel::None,
+            time_origin: 0.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, Array1};
+
+    use super::*;
+    use crate::{util::get_text_from_child, Format, Values};
+
+    #[test]
+    fn test_roundtrip() {
+        let data = array![[1.0_f32, 2.0], [3.0, 4.0], [5.0, 6.0]];
+        let timestamps = array![0.0, 0.5, f64::NAN];
+
+        let stream = Stream::from_ndarray(data.view(), timestamps.view(), Some(2.0)).unwrap();
+        assert_eq!(stream.format, Format::Float32);
+        assert_eq!(stream.channel_count, 2);
+        assert_eq!(stream.samples[1].values, Values::Float32(vec![3.0, 4.0]));
+        assert_eq!(stream.samples[2].timestamp, None);
+        assert_eq!(stream.recorded_timestamps, vec![0, 1]);
+        assert_eq!(
+            get_text_from_child(&stream.header, "channel_format").unwrap(),
+            "float32"
+        );
+        assert_eq!(get_text_from_child(&stream.header, "nominal_srate").unwrap(), "2");
+
+        let (data_out, timestamps_out) = stream.to_ndarray::<f32>().unwrap();
+        assert_eq!(data_out, data);
+        assert_eq!(timestamps_out.iter().take(2).collect::<Vec<_>>(), vec![&0.0, &0.5]);
+        assert!(timestamps_out[2].is_nan());
+    }
+
+    #[test]
+    fn test_errors() {
+        let data = array![[1_i8], [2]];
+
+        assert!(matches!(
+            Stream::from_ndarray(data.view(), Array1::zeros(3).view(), None),
+            Err(StreamError::MismatchedTimestamps {
+                samples: 2,
+                timestamps: 3
+            })
+        ));
+        assert!(matches!(
+            Stream::from_ndarray(data.view(), Array1::zeros(2).view(), Some(-1.0)),
+            Err(StreamError::InvalidSrate(_))
+        ));
+
+        let stream = Stream::from_ndarray(data.view(), Array1::zeros(2).view(), None).unwrap();
+        assert!(matches!(
+            stream.to_ndarray::<i16>(),
+            Err(StreamError::WrongFormat { .. })
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use xmltree::Element;
+
+use crate::util::{get_text_from_child, parse_child};
+
+/**
+Metadata of a single channel, as found in the `<desc><channels><channel>` block of a stream header.
+
+The contents of `desc` are not standardised, this follows the conventions of LSL and its
+[meta-data recommendations](https://github.com/sccn/xdf/wiki/Meta-Data). All fields are optional.
+*/
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelInfo {
+    /// The label of the channel, for example `Cz` or `AUX1`.
+    pub label: Option<String>,
+    /// The unit of the channel's values, for example `microvolts`.
+    pub unit: Option<String>,
+    /// The type of the channel, for example `EEG`, `EOG` or `Trigger`.
+    pub r#type: Option<String>,
+    /// The position of the channel as X, Y and Z coordinates, if all three are given as numbers.
+    pub location: Option<[f64; 3]>,
+    /// The text of all other child elements of `<channel>` by name.
+    /// Nested elements are not included, they are still available through [`crate::Stream::header`].
+    pub extra: HashMap<String, String>,
+}
+
+impl ChannelInfo {
+    fn from_element(channel: &Element) -> Self {
+        let text = |name: &str| get_text_from_child(channel, name).ok();
+
+        let location = channel.get_child("location").and_then(|location| {
+            Some([
+                parse_child(location, "X")?,
+                parse_child(location, "Y")?,
+                parse_child(location, "Z")?,
+            ])
+        });
+
+        let extra = channel
+            .children
+            .iter()
+            .filter_map(|node| node.as_element())
+            .filter(|element| !matches!(element.name.as_str(), "label" | "unit" | "type" | "location"))
+            .filter_map(|element| Some((element.name.clone(), element.get_text()?.into_owned())))
+            .collect();
+
+        Self {
+            label: text("label"),
+            unit: text("unit"),
+            r#type: text("type"),
+            location,
+            extra,
+        }
+    }
+}
+
+// parses the channels in desc/channels of a stream header. Empty if there are none.
+pub(crate) fn parse_channels(header: &Element) -> Vec<ChannelInfo> {
+    let Some(channels) = header.get_child("desc").and_then(|desc| desc.get_child("channels")) else {
+        return Vec::new();
+    };
+
+    channels
+        .children
+        .iter()
+        .filter_map(|node| node.as_element())
+        .filter(|element| element.name == "channel")
+        .map(ChannelInfo::from_element)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_channels() {
+        let header = Element::parse(
+            r"<info>
+                <desc>
+                    <channels>
+                        <channel>
+                            <label>Cz</label>
+                            <unit>microvolts</unit>
+                            <type>EEG</type>
+                            <location><X>0</X><Y>0.5</Y><Z>1</Z></location>
+                            <impedance>5</impedance>
+                        </channel>
+                        <channel>
+                            <label>AUX</label>
+                            <location><X>1</X></location>
+                        </channel>
+                    </channels>
+                </desc>
+            </info>"
+                .as_bytes(),
+        )
+        .unwrap();
+
+        let channels = parse_channels(&header);
+        assert_eq!(channels.len(), 2);
+
+        assert_eq!(channels[0].label.as_deref(), Some("Cz"));
+        assert_eq!(channels[0].unit.as_deref(), Some("microvolts"));
+        assert_eq!(channels[0].r#type.as_deref(), Some("EEG"));
+        assert_eq!(channels[0].location, Some([0.0, 0.5, 1.0]));
+        assert_eq!(
+            channels[0].extra,
+            HashMap::from([("impedance".to_string(), "5".to_string())])
+        );
+
+        assert_eq!(channels[1].label.as_deref(), Some("AUX"));
+        assert_eq!(channels[1].unit, None);
+        assert_eq!(channels[1].location, None);
+    }
+
+    #[test]
+    fn test_parse_channels_empty_desc() {
+        let header = Element::parse("<info><desc/></info>".as_bytes()).unwrap();
+        assert!(parse_channels(&header).is_empty());
+
+        let header = Element::parse("<info/>".as_bytes()).unwrap();
+        assert!(parse_channels(&header).is_empty());
+    }
+}
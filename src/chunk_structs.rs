@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 
 use xmltree::Element;
 
-use crate::{Format, Sample};
+use crate::{Format, Sample, Values};
 
 #[derive(Debug)]
 pub(crate) enum Chunk {
@@ -12,11 +12,12 @@ pub(crate) enum Chunk {
     ClockOffset(ClockOffsetChunk),
     Boundary(BoundaryChunk),
     StreamFooter(StreamFooterChunk),
+    Unknown(UnknownChunk),
 }
 
 #[derive(Debug)]
-#[doc = "The FileHeaderChunk is the first chunk in an XDF file. It contains the version of the XDF file format and an XML element that contains additional information about the file."]
-#[doc = "There must be exactly one FileHeaderChunk in an XDF file."]
+#[doc = "The `FileHeaderChunk` is the first chunk in an XDF file. It contains the version of the XDF file format and an XML element that contains additional information about the file."]
+#[doc = "There must be exactly one `FileHeaderChunk` in an XDF file."]
 pub(crate) struct FileHeaderChunk {
     /// The version of the XDF file format. Currently, only version 1.0 is supported.
     pub version: f32,
@@ -51,10 +52,100 @@ pub(crate) struct StreamHeaderChunk {
     pub xml: Element,
 }
 
+// the values of all samples in a chunk as one contiguous vector of length samples × channels, sample by sample
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ChunkValues {
+    Int8(Vec<i8>),
+    Int16(Vec<i16>),
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    Float32(Vec<f32>),
+    Float64(Vec<f64>),
+    String(Vec<String>),
+}
+
+impl ChunkValues {
+    pub(crate) fn with_capacity(format: Format, capacity: usize) -> Self {
+        match format {
+            Format::Int8 => Self::Int8(Vec::with_capacity(capacity)),
+            Format::Int16 => Self::Int16(Vec::with_capacity(capacity)),
+            Format::Int32 => Self::Int32(Vec::with_capacity(capacity)),
+            Format::Int64 => Self::Int64(Vec::with_capacity(capacity)),
+            Format::Float32 => Self::Float32(Vec::with_capacity(capacity)),
+            Format::Float64 => Self::Float64(Vec::with_capacity(capacity)),
+            Format::String => Self::String(Vec::with_capacity(capacity)),
+        }
+    }
+
+    // the values at `start..start + len` as the values of a single sample. Strings are moved out of the buffer.
+    fn take_row(&mut self, start: usize, len: usize) -> Values {
+        fn row<T: Clone>(values: &[T], start: usize, len: usize) -> Vec<T> {
+            values.get(start..start + len).map(<[T]>::to_vec).unwrap_or_default()
+        }
+
+        match self {
+            Self::Int8(v) => Values::Int8(row(v, start, len)),
+            Self::Int16(v) => Values::Int16(row(v, start, len)),
+            Self::Int32(v) => Values::Int32(row(v, start, len)),
+            Self::Int64(v) => Values::Int64(row(v, start, len)),
+            Self::Float32(v) => Values::Float32(row(v, start, len)),
+            Self::Float64(v) => Values::Float64(row(v, start, len)),
+            Self::String(v) => Values::String(
+                v.get_mut(start..start + len)
+                    .map(|strings| strings.iter_mut().map(std::mem::take).collect())
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+// A decoded samples chunk. The values are kept in a single buffer instead of one allocation per sample
+// so that large chunks can be decoded in bulk.
 #[derive(Debug)]
 pub(crate) struct SamplesChunk {
     pub stream_id: u32,
-    pub samples: Vec<Sample>,
+    pub num_channels: usize,
+    pub timestamps: Vec<Option<f64>>,
+    pub values: ChunkValues,
+}
+
+impl IntoIterator for SamplesChunk {
+    type Item = Sample;
+    type IntoIter = ChunkSamples;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ChunkSamples {
+            timestamps: self.timestamps.into_iter(),
+            values: self.values,
+            num_channels: self.num_channels,
+            next_value: 0,
+        }
+    }
+}
+
+// iterates over the samples of a chunk, splitting its buffer into the values of each sample
+#[derive(Debug)]
+pub(crate) struct ChunkSamples {
+    timestamps: std::vec::IntoIter<Option<f64>>,
+    values: ChunkValues,
+    num_channels: usize,
+    next_value: usize,
+}
+
+impl Iterator for ChunkSamples {
+    type Item = Sample;
+
+    fn next(&mut self) -> Option<Sample> {
+        let timestamp = self.timestamps.next()?;
+        let values = self.values.take_row(self.next_value, self.num_channels);
+        self.next_value += self.num_channels;
+
+        Some(Sample { timestamp, values })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.timestamps.size_hint()
+    }
 }
 
 //collection_time and offset_value are in seconds
@@ -82,6 +173,21 @@ pub(crate) struct StreamFooterChunk {
     pub xml: Element,
 }
 
+/**
+A chunk whose tag is not defined by the XDF specification, for example one added by a vendor or a newer version
+of the format. Such chunks are skipped while parsing and kept as they are in [`crate::XDFFile::unknown_chunks`].
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnknownChunk {
+    /// The tag of the chunk.
+    pub tag: u16,
+    /// The position of the chunk in the file, in bytes from the start of the file.
+    pub offset: usize,
+    /// The content of the chunk following the tag.
+    pub bytes: Vec<u8>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum Tag {
     FileHeader,
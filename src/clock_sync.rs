@@ -0,0 +1,411 @@
+use std::{fmt, sync::Arc};
+
+use crate::chunk_structs::ClockOffsetChunk;
+
+/// A single clock offset measurement of a stream, as recorded in the file's clock offset chunks.
+///
+/// Adding `offset_value` to a timestamp of the stream's clock at `collection_time`
+/// converts it into the clock of the recording computer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClockOffset {
+    /// The time at which the offset was measured, in seconds of the stream's clock.
+    pub collection_time: f64,
+    /// The measured offset in seconds.
+    pub offset_value: f64,
+}
+
+impl From<ClockOffsetChunk> for ClockOffset {
+    fn from(chunk: ClockOffsetChunk) -> Self {
+        Self {
+            collection_time: chunk.collection_time,
+            offset_value: chunk.offset_value,
+        }
+    }
+}
+
+/**
+A strategy for turning the clock offsets of a stream into a [`ClockModel`] which is then used to correct its timestamps.
+
+The strategy is chosen through [`crate::ParseOptions::clock_sync`]. This crate provides
+[`PiecewiseLinearSync`] (the default), [`StepSync`], [`NearestSync`], [`LeastSquaresSync`] and [`NoSync`].
+Custom implementations can build a model from other sources, for example by returning
+[`ClockModel::PiecewiseLinear`] with offsets derived from hardware triggers, or any offset function
+as a [`ClockModel::Custom`].
+
+# Example
+```rust
+# use std::sync::Arc;
+# use xdf::{ClockModel, ClockOffset, ClockSync};
+// corrects all streams by a fixed latency of the amplifiers
+#[derive(Debug)]
+struct AmplifierLatency(f64);
+
+impl ClockSync for AmplifierLatency {
+    fn fit(&self, _stream_id: u32, _offsets: &[ClockOffset]) -> ClockModel {
+        let latency = self.0;
+        ClockModel::Custom(Arc::new(move |_time| -latency))
+    }
+}
+
+let model = AmplifierLatency(0.02).fit(0, &[]);
+assert_eq!(model.offset_at(10.0), -0.02);
+```
+*/
+pub trait ClockSync: std::fmt::Debug + Send + Sync {
+    /// Fits a model to the clock offsets of the stream with the given `stream_id`.
+    /// The offsets are finite and sorted by collection time. They may be empty.
+    fn fit(&self, stream_id: u32, offsets: &[ClockOffset]) -> ClockModel;
+}
+
+/// Interpolates linearly between the clock offsets, see [`ClockModel::PiecewiseLinear`]. This is the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PiecewiseLinearSync;
+
+impl ClockSync for PiecewiseLinearSync {
+    fn fit(&self, _stream_id: u32, offsets: &[ClockOffset]) -> ClockModel {
+        if offsets.is_empty() {
+            ClockModel::None
+        } else {
+            ClockModel::PiecewiseLinear(offsets.to_vec())
+        }
+    }
+}
+
+/// Holds each clock offset until the next one, see [`ClockModel::Step`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StepSync;
+
+impl ClockSync for StepSync {
+    fn fit(&self, _stream_id: u32, offsets: &[ClockOffset]) -> ClockModel {
+        if offsets.is_empty() {
+            ClockModel::None
+        } else {
+            ClockModel::Step(offsets.to_vec())
+        }
+    }
+}
+
+/// Uses the clock offset with the closest collection time, see [`ClockModel::Nearest`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NearestSync;
+
+impl ClockSync for NearestSync {
+    fn fit(&self, _stream_id: u32, offsets: &[ClockOffset]) -> ClockModel {
+        if offsets.is_empty() {
+            ClockModel::None
+        } else {
+            ClockModel::Nearest(offsets.to_vec())
+        }
+    }
+}
+
+/// Fits a single line through all clock offsets using least squares, see [`ClockModel::Linear`].
+/// This is less sensitive to jitter in the individual measurements than interpolation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LeastSquaresSync;
+
+impl ClockSync for LeastSquaresSync {
+    fn fit(&self, _stream_id: u32, offsets: &[ClockOffset]) -> ClockModel {
+        if offsets.is_empty() {
+            return ClockModel::None;
+        }
+
+        let n = offsets.len() as f64;
+        let mean_time = offsets.iter().map(|o| o.collection_time).sum::<f64>() / n;
+        let mean_value = offsets.iter().map(|o| o.offset_value).sum::<f64>() / n;
+
+        // centering the times first avoids cancellation, collection times are typically large
+        let (covariance, variance) = offsets.iter().fold((0.0, 0.0), |(cov, var), o| {
+            let dt = o.collection_time - mean_time;
+            (cov + dt * (o.offset_value - mean_value), var + dt * dt)
+        });
+
+        // a single offset or offsets which were all collected at the same time
+        let slope = if variance > 0.0 { covariance / variance } else { 0.0 };
+
+        ClockModel::Linear {
+            intercept: mean_value - slope * mean_time,
+            slope,
+        }
+    }
+}
+
+/// Does not correct the timestamps at all.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoSync;
+
+impl ClockSync for NoSync {
+    fn fit(&self, _stream_id: u32, _offsets: &[ClockOffset]) -> ClockModel {
+        ClockModel::None
+    }
+}
+
+/**
+The clock synchronisation model which was applied to the timestamps of a stream.
+
+Models are compared by value, except for [`ClockModel::Custom`] which is only equal to itself.
+*/
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClockModel {
+    /// The timestamps were not corrected, for example because the stream has no clock offsets.
+    None,
+    /// The offsets were interpolated linearly between these points.
+    /// Before the first and after the last point, the first and last offset respectively are used.
+    PiecewiseLinear(Vec<ClockOffset>),
+    /// Each offset was used from its collection time until the next one.
+    /// Before the first point, the first offset is used.
+    Step(Vec<ClockOffset>),
+    /// Each offset was used for the times closer to its collection time than to any other.
+    /// Halfway between two points, the later offset is used.
+    Nearest(Vec<ClockOffset>),
+    /// The offset is given by `intercept + slope * time`.
+    Linear {
+        /// The offset at time zero of the stream's clock.
+        intercept: f64,
+        /// The clock drift in seconds per second.
+        slope: f64,
+    },
+    /// The offset is given by a function of the time, supplied by a custom [`ClockSync`].
+    /// This variant is skipped by serde, serializing it fails.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Arc<dyn Fn(f64) -> f64 + Send + Sync + std::panic::RefUnwindSafe>),
+}
+
+impl fmt::Debug for ClockModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => f.write_str("None"),
+            Self::PiecewiseLinear(offsets) => f.debug_tuple("PiecewiseLinear").field(offsets).finish(),
+            Self::Step(offsets) => f.debug_tuple("Step").field(offsets).finish(),
+            Self::Nearest(offsets) => f.debug_tuple("Nearest").field(offsets).finish(),
+            Self::Linear { intercept, slope } => f
+                .debug_struct("Linear")
+                .field("intercept", intercept)
+                .field("slope", slope)
+                .finish(),
+            Self::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
+}
+
+impl PartialEq for ClockModel {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::None, Self::None) => true,
+            (Self::PiecewiseLinear(a), Self::PiecewiseLinear(b))
+            | (Self::Step(a), Self::Step(b))
+            | (Self::Nearest(a), Self::Nearest(b)) => a == b,
+            (
+                Self::Linear { intercept, slope },
+                Self::Linear {
+                    intercept: other_intercept,
+                    slope: other_slope,
+                },
+            ) => intercept == other_intercept && slope == other_slope,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl ClockModel {
+    /// Returns the offset this model adds to a timestamp `time` of the stream's clock.
+    #[must_use]
+    pub fn offset_at(&self, time: f64) -> f64 {
+        let mut offset_index = match self {
+            Self::PiecewiseLinear(offsets) | Self::Step(offsets) | Self::Nearest(offsets) => {
+                offsets.partition_point(|o| o.collection_time < time).saturating_sub(1)
+            }
+            Self::None | Self::Linear { .. } | Self::Custom(_) => 0,
+        };
+
+        self.offset_from_index(time, &mut offset_index)
+    }
+
+    // like offset_at but continues searching for the right offsets from `offset_index`,
+    // which makes it cheap to correct many timestamps in ascending order.
+    pub(crate) fn offset_from_index(&self, time: f64, offset_index: &mut usize) -> f64 {
+        match self {
+            Self::None => 0.0,
+            Self::PiecewiseLinear(offsets) => crate::interpolate_offset(time, offsets, offset_index),
+            Self::Step(offsets) => match previous_offset(time, offsets, offset_index) {
+                Some(previous) => previous.offset_value,
+                None => offsets.first().map_or(0.0, |first| first.offset_value),
+            },
+            Self::Nearest(offsets) => {
+                let Some(previous) = previous_offset(time, offsets, offset_index) else {
+                    return offsets.first().map_or(0.0, |first| first.offset_value);
+                };
+                match offsets.get(*offset_index + 1) {
+                    Some(next) if next.collection_time - time <= time - previous.collection_time => next.offset_value,
+                    _ => previous.offset_value,
+                }
+            }
+            Self::Linear { intercept, slope } => intercept + slope * time,
+            Self::Custom(offset) => offset(time),
+        }
+    }
+}
+
+// the last offset collected at or before `time`, continuing the search from `offset_index`.
+// None if `time` is before the first offset.
+fn previous_offset<'a>(time: f64, offsets: &'a [ClockOffset], offset_index: &mut usize) -> Option<&'a ClockOffset> {
+    if offsets.first().is_none_or(|first| time < first.collection_time) {
+        return None;
+    }
+
+    // the timestamps are not guaranteed to be in order, start over if we have gone too far
+    if offsets.get(*offset_index).is_none_or(|o| o.collection_time > time) {
+        *offset_index = 0;
+    }
+    while offsets
+        .get(*offset_index + 1)
+        .is_some_and(|o| o.collection_time <= time)
+    {
+        *offset_index += 1;
+    }
+
+    offsets.get(*offset_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1E-14;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_offset_at() {
+        let offsets = vec![
+            ClockOffset {
+                collection_time: 0.0,
+                offset_value: -1.0,
+            },
+            ClockOffset {
+                collection_time: 1.0,
+                offset_value: 1.0,
+            },
+            ClockOffset {
+                collection_time: 3.0,
+                offset_value: 2.0,
+            },
+        ];
+        let model = ClockModel::PiecewiseLinear(offsets);
+
+        assert_eq!(model.offset_at(-5.0), -1.0);
+        assert!(model.offset_at(0.5).abs() < EPSILON);
+        assert!((model.offset_at(2.0) - 1.5).abs() < EPSILON);
+        assert_eq!(model.offset_at(10.0), 2.0);
+
+        assert_eq!(ClockModel::None.offset_at(1.0), 0.0);
+    }
+
+    fn test_offsets() -> Vec<ClockOffset> {
+        vec![
+            ClockOffset {
+                collection_time: 0.0,
+                offset_value: -1.0,
+            },
+            ClockOffset {
+                collection_time: 1.0,
+                offset_value: 1.0,
+            },
+            ClockOffset {
+                collection_time: 3.0,
+                offset_value: 2.0,
+            },
+        ]
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_step_sync() {
+        let model = StepSync.fit(0, &test_offsets());
+
+        assert_eq!(model.offset_at(-1.0), -1.0);
+        assert_eq!(model.offset_at(0.5), -1.0);
+        assert_eq!(model.offset_at(1.0), 1.0);
+        assert_eq!(model.offset_at(2.9), 1.0);
+        assert_eq!(model.offset_at(4.0), 2.0);
+
+        // going backwards in time must not get stuck on a later offset
+        let mut offset_index = 0;
+        assert_eq!(model.offset_from_index(4.0, &mut offset_index), 2.0);
+        assert_eq!(model.offset_from_index(0.5, &mut offset_index), -1.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_nearest_sync() {
+        let model = NearestSync.fit(0, &test_offsets());
+
+        assert_eq!(model.offset_at(-1.0), -1.0);
+        assert_eq!(model.offset_at(0.4), -1.0);
+        assert_eq!(model.offset_at(0.5), 1.0);
+        assert_eq!(model.offset_at(1.9), 1.0);
+        assert_eq!(model.offset_at(2.1), 2.0);
+        assert_eq!(model.offset_at(4.0), 2.0);
+
+        let mut offset_index = 0;
+        assert_eq!(model.offset_from_index(2.5, &mut offset_index), 2.0);
+        assert_eq!(model.offset_from_index(0.2, &mut offset_index), -1.0);
+    }
+
+    #[test]
+    fn test_least_squares_sync() {
+        // offsets on a perfect line are reproduced exactly
+        let offsets: Vec<ClockOffset> = (0..10)
+            .map(|i| ClockOffset {
+                collection_time: 50_000.0 + f64::from(i) * 5.0,
+                offset_value: 0.25 + 1E-5 * f64::from(i) * 5.0,
+            })
+            .collect();
+
+        let ClockModel::Linear { slope, .. } = LeastSquaresSync.fit(0, &offsets) else {
+            panic!("expected a linear model");
+        };
+        assert!((slope - 1E-5).abs() < 1E-12);
+
+        let model = LeastSquaresSync.fit(0, &offsets);
+        for o in &offsets {
+            assert!((model.offset_at(o.collection_time) - o.offset_value).abs() < 1E-9);
+        }
+
+        // a single offset is held constant
+        let model = LeastSquaresSync.fit(0, &offsets[..1]);
+        assert!((model.offset_at(0.0) - 0.25).abs() < EPSILON);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_custom_model() {
+        #[derive(Debug)]
+        struct TriggerSync;
+
+        impl ClockSync for TriggerSync {
+            fn fit(&self, _stream_id: u32, _offsets: &[ClockOffset]) -> ClockModel {
+                ClockModel::Custom(Arc::new(|time| if time < 10.0 { 0.5 } else { 0.75 }))
+            }
+        }
+
+        let model = TriggerSync.fit(0, &test_offsets());
+        assert_eq!(model.offset_at(1.0), 0.5);
+        assert_eq!(model.offset_at(12.0), 0.75);
+        assert_eq!(format!("{model:?}"), "Custom(..)");
+
+        assert_eq!(model, model.clone());
+        assert_ne!(model, TriggerSync.fit(0, &test_offsets()));
+    }
+
+    #[test]
+    fn test_empty_offsets() {
+        assert_eq!(PiecewiseLinearSync.fit(0, &[]), ClockModel::None);
+        assert_eq!(StepSync.fit(0, &[]), ClockModel::None);
+        assert_eq!(NearestSync.fit(0, &[]), ClockModel::None);
+        assert_eq!(LeastSquaresSync.fit(0, &[]), ClockModel::None);
+        assert_eq!(NoSync.fit(0, &test_offsets()), ClockModel::None);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{errors::StreamError, Format, Sample, Stream, Values, XDFFile};
+
+/// The values of a stream stored channel-major, i.e. as one contiguous vector per channel.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Columns {
+    Int8(Vec<Vec<i8>>),
+    Int16(Vec<Vec<i16>>),
+    Int32(Vec<Vec<i32>>),
+    Int64(Vec<Vec<i64>>),
+    Float32(Vec<Vec<f32>>),
+    Float64(Vec<Vec<f64>>),
+    String(Vec<Vec<String>>),
+}
+
+impl Columns {
+    fn with_capacity(format: Format, channel_count: usize, capacity: usize) -> Self {
+        fn empty<T>(channel_count: usize, capacity: usize) -> Vec<Vec<T>> {
+            (0..channel_count).map(|_| Vec::with_capacity(capacity)).collect()
+        }
+
+        match format {
+            Format::Int8 => Self::Int8(empty(channel_count, capacity)),
+            Format::Int16 => Self::Int16(empty(channel_count, capacity)),
+            Format::Int32 => Self::Int32(empty(channel_count, capacity)),
+            Format::Int64 => Self::Int64(empty(channel_count, capacity)),
+            Format::Float32 => Self::Float32(empty(channel_count, capacity)),
+            Format::Float64 => Self::Float64(empty(channel_count, capacity)),
+            Format::String => Self::String(empty(channel_count, capacity)),
+        }
+    }
+
+    /// The number of channels.
+    #[must_use]
+    pub fn channel_count(&self) -> usize {
+        match self {
+            Self::Int8(c) => c.len(),
+            Self::Int16(c) => c.len(),
+            Self::Int32(c) => c.len(),
+            Self::Int64(c) => c.len(),
+            Self::Float32(c) => c.len(),
+            Self::Float64(c) => c.len(),
+            Self::String(c) => c.len(),
+        }
+    }
+
+    /// The number of samples, i.e. the length of each channel.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Int8(c) => c.first().map_or(0, Vec::len),
+            Self::Int16(c) => c.first().map_or(0, Vec::len),
+            Self::Int32(c) => c.first().map_or(0, Vec::len),
+            Self::Int64(c) => c.first().map_or(0, Vec::len),
+            Self::Float32(c) => c.first().map_or(0, Vec::len),
+            Self::Float64(c) => c.first().map_or(0, Vec::len),
+            Self::String(c) => c.first().map_or(0, Vec::len),
+        }
+    }
+
+    /// Returns true if there are no samples.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // appends one sample's values, returning false if they do not fit these columns
+    fn push(&mut self, values: Values) -> bool {
+        fn push_row<T>(columns: &mut [Vec<T>], row: Vec<T>) -> bool {
+            if columns.len() != row.len() {
+                return false;
+            }
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+            true
+        }
+
+        match (self, values) {
+            (Self::Int8(c), Values::Int8(v)) => push_row(c, v),
+            (Self::Int16(c), Values::Int16(v)) => push_row(c, v),
+            (Self::Int32(c), Values::Int32(v)) => push_row(c, v),
+            (Self::Int64(c), Values::Int64(v)) => push_row(c, v),
+            (Self::Float32(c), Values::Float32(v)) => push_row(c, v),
+            (Self::Float64(c), Values::Float64(v)) => push_row(c, v),
+            (Self::String(c), Values::String(v)) => push_row(c, v),
+            _ => false,
+        }
+    }
+
+    /// Returns the values of the sample at `index` as a row, or None if it is out of bounds.
+    #[must_use]
+    pub fn row(&self, index: usize) -> Option<Values> {
+        fn row_of<T: Clone>(columns: &[Vec<T>], index: usize) -> Option<Vec<T>> {
+            columns.iter().map(|column| column.get(index).cloned()).collect()
+        }
+
+        if index >= self.len() {
+            return None;
+        }
+
+        match self {
+            Self::Int8(c) => row_of(c, index).map(Values::Int8),
+            Self::Int16(c) => row_of(c, index).map(Values::Int16),
+            Self::Int32(c) => row_of(c, index).map(Values::Int32),
+            Self::Int64(c) => row_of(c, index).map(Values::Int64),
+            Self::Float32(c) => row_of(c, index).map(Values::Float32),
+            Self::Float64(c) => row_of(c, index).map(Values::Float64),
+            Self::String(c) => row_of(c, index).map(Values::String),
+        }
+    }
+}
+
+/**
+A stream whose samples are stored channel-major in [`Columns`] rather than as a vector of [`Sample`]s.
+This avoids one allocation per sample and keeps each channel contiguous in memory, which suits per-channel processing.
+
+Convert a parsed [`Stream`] with [`ColumnarStream::try_from`] and back with [`Stream::from`].
+*/
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColumnarStream {
+    /// The metadata of the stream. Its [`Stream::samples`] are empty, they are stored in `timestamps` and `columns` instead.
+    pub stream: Stream,
+    /// The timestamp of each sample.
+    pub timestamps: Vec<Option<f64>>,
+    /// The values of each channel.
+    pub columns: Columns,
+}
+
+impl TryFrom<Stream> for ColumnarStream {
+    type Error = StreamError;
+
+    /// Moves the samples of `stream` into columns, freeing each sample as it goes.
+    ///
+    /// # Errors
+    /// [`StreamError::MismatchedValues`] if the values of a sample do not match the stream's format or channel count.
+    fn try_from(mut stream: Stream) -> Result<Self, Self::Error> {
+        let samples = std::mem::take(&mut stream.samples);
+        let channel_count = stream.channel_count as usize;
+
+        let mut timestamps = Vec::with_capacity(samples.len());
+        let mut columns = Columns::with_capacity(stream.format, channel_count, samples.len());
+
+        for sample in samples {
+            timestamps.push(sample.timestamp);
+            if !columns.push(sample.values) {
+                return Err(StreamError::MismatchedValues(stream.format));
+            }
+        }
+
+        Ok(Self {
+            stream,
+            timestamps,
+            columns,
+        })
+    }
+}
+
+impl From<ColumnarStream> for Stream {
+    fn from(columnar: ColumnarStream) -> Self {
+        let ColumnarStream {
+            mut stream,
+            timestamps,
+            columns,
+        } = columnar;
+
+        stream.samples = timestamps
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, timestamp)| columns.row(i).map(|values| Sample { timestamp, values }))
+            .collect();
+
+        stream
+    }
+}
+
+impl XDFFile {
+    /// Converts all streams into [`ColumnarStream`]s, one stream at a time.
+    ///
+    /// # Errors
+    /// See [`ColumnarStream::try_from`].
+    pub fn into_columnar_streams(self) -> Result<Vec<ColumnarStream>, StreamError> {
+        self.streams.into_iter().map(ColumnarStream::try_from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int_stream() -> Stream {
+        let samples = (0..4).map(|i| (f64::from(i), Values::Int16(vec![i, 10 + i, 20 + i])));
+        Stream::from_test_timestamps(Format::Int16, 3, Some(1.0), samples)
+    }
+
+    #[test]
+    fn test_columns_are_channel_major() {
+        let columnar = ColumnarStream::try_from(int_stream()).unwrap();
+
+        assert!(columnar.stream.samples.is_empty());
+        assert_eq!(columnar.timestamps, vec![Some(0.0), Some(1.0), Some(2.0), Some(3.0)]);
+        assert_eq!(columnar.columns.channel_count(), 3);
+        assert_eq!(columnar.columns.len(), 4);
+        assert_eq!(
+            columnar.columns,
+            Columns::Int16(vec![vec![0, 1, 2, 3], vec![10, 11, 12, 13], vec![20, 21, 22, 23]])
+        );
+        assert_eq!(columnar.columns.row(2), Some(Values::Int16(vec![2, 12, 22])));
+        assert_eq!(columnar.columns.row(4), None);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let stream = int_stream();
+        let columnar = ColumnarStream::try_from(stream.clone()).unwrap();
+        assert_eq!(Stream::from(columnar), stream);
+
+        let marker_stream = Stream::from_test_timestamps(
+            Format::String,
+            2,
+            None,
+            [(
+                None::<f64>,
+                Values::String(vec!["marker".to_string(), "condition".to_string()]),
+            )],
+        );
+        let columnar = ColumnarStream::try_from(marker_stream.clone()).unwrap();
+        assert_eq!(
+            columnar.columns,
+            Columns::String(vec![vec!["marker".to_string()], vec!["condition".to_string()]])
+        );
+        assert_eq!(Stream::from(columnar), marker_stream);
+    }
+
+    #[test]
+    fn test_mismatched_values() {
+        let mut stream = int_stream();
+        stream.samples[1].values = Values::Int16(vec![1, 2]);
+        assert!(matches!(
+            ColumnarStream::try_from(stream),
+            Err(StreamError::MismatchedValues(Format::Int16))
+        ));
+
+        let mut stream = int_stream();
+        stream.samples[1].values = Values::Float32(vec![1.0, 2.0, 3.0]);
+        assert!(ColumnarStream::try_from(stream).is_err());
+    }
+}
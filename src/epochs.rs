@@ -0,0 +1,265 @@
+use crate::{errors::StreamError, Event, Format, Stream};
+
+/// Fixed-length windows of a numeric stream, time-locked to events. Created with [`Stream::epochs`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Epochs {
+    /// The time of each sample of an epoch relative to its event, in seconds.
+    pub times: Vec<f64>,
+    /// The number of channels of each sample.
+    pub channel_count: usize,
+    /// The values of all epochs in one contiguous vector, ordered by epoch, then sample, then channel.
+    /// Channel `c` of sample `s` in epoch `e` is at `(e * times.len() + s) * channel_count + c`, see [`Epochs::epoch`].
+    pub data: Vec<f64>,
+    /// The event each epoch is locked to, i.e. `events[i]` belongs to `epoch(i)`.
+    pub events: Vec<Event>,
+    /// The window relative to the events which was used for baseline correction, if any.
+    pub baseline: Option<(f64, f64)>,
+}
+
+impl Epochs {
+    /// The number of epochs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns true if there are no epochs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// The number of epochs, samples per epoch and channels, i.e. the shape of [`Epochs::data`].
+    #[must_use]
+    pub fn shape(&self) -> (usize, usize, usize) {
+        (self.len(), self.times.len(), self.channel_count)
+    }
+
+    /// Returns the values of the epoch at `index`, ordered by sample, then channel. None if `index` is out of range.
+    #[must_use]
+    pub fn epoch(&self, index: usize) -> Option<&[f64]> {
+        let epoch_len = self.times.len() * self.channel_count;
+        self.data.get(index * epoch_len..(index + 1) * epoch_len)
+    }
+}
+
+impl Stream {
+    /**
+    Cuts a window from `tmin` to `tmax` seconds relative to each event out of this regularly sampled numeric stream,
+    for example -0.2 to 0.8 around every stimulus marker. The events typically come from [`crate::XDFFile::events`].
+
+    The windows are placed on the stream's samples, so the first sample of each epoch is the one closest to `time + tmin`.
+    Events whose window is not fully covered by contiguous samples, for example near the start or end of the recording
+    or across a gap, are left out, as are epochs with samples which do not match the channel count.
+
+    # Arguments
+    * `events` - The events to lock the epochs to.
+    * `tmin`, `tmax` - The window relative to each event in seconds, both inclusive.
+    * `baseline` - If given, the mean of each channel over this window (relative to the event, inclusive)
+      is subtracted from the epoch.
+
+    # Errors
+    * [`StreamError::UnsupportedFormat`] for string streams.
+    * [`StreamError::InvalidSrate`] for streams without a nominal sampling rate.
+    * [`StreamError::InvalidWindow`] if `tmin` is after `tmax` or not finite,
+      or if the baseline window contains no samples of the epoch.
+    */
+    pub fn epochs(
+        &self,
+        events: &[Event],
+        tmin: f64,
+        tmax: f64,
+        baseline: Option<(f64, f64)>,
+    ) -> Result<Epochs, StreamError> {
+        if self.format == Format::String {
+            return Err(StreamError::UnsupportedFormat(self.format));
+        }
+        let srate = self.nominal_srate.ok_or(StreamError::InvalidSrate(0.0))?;
+        if !(tmin.is_finite() && tmax.is_finite()) || tmin > tmax {
+            return Err(StreamError::InvalidWindow { start: tmin, end: tmax });
+        }
+
+        let times = window_times(tmin, tmax, srate);
+        // both exist since tmin <= tmax
+        let (first_time, last_time) = (times[0], times[times.len() - 1]);
+
+        let baseline_range = match baseline {
+            Some((start, end)) => {
+                let first = times.partition_point(|&t| t < start);
+                let last = times.partition_point(|&t| t <= end);
+                if first >= last {
+                    return Err(StreamError::InvalidWindow { start, end });
+                }
+                Some(first..last)
+            }
+            None => None,
+        };
+
+        let tolerance = 0.5 / srate;
+        let is_close = |index: usize, time: f64| {
+            self.samples
+                .get(index)
+                .and_then(|s| s.timestamp)
+                .is_some_and(|ts| (ts - time).abs() <= tolerance)
+        };
+
+        let channel_count = self.channel_count as usize;
+        let mut data = Vec::new();
+        let mut kept_events = Vec::new();
+
+        for event in events {
+            let Some(start) = self.nearest_index(event.time + first_time) else {
+                continue;
+            };
+            let end = start + times.len() - 1;
+            if !is_close(start, event.time + first_time) || !is_close(end, event.time + last_time) {
+                continue;
+            }
+
+            let epoch_start = data.len();
+            let complete = self.samples[start..=end].iter().all(|s| match s.values.to_f64_vec() {
+                Some(values) if values.len() == channel_count => {
+                    data.extend(values);
+                    true
+                }
+                _ => false,
+            });
+            if !complete {
+                data.truncate(epoch_start);
+                continue;
+            }
+
+            if let Some(range) = &baseline_range {
+                subtract_baseline(&mut data[epoch_start..], channel_count, range.clone());
+            }
+
+            kept_events.push(event.clone());
+        }
+
+        Ok(Epochs {
+            times,
+            channel_count,
+            data,
+            events: kept_events,
+            baseline,
+        })
+    }
+}
+
+// the sample times from tmin to tmax on the grid k / srate around the event, so zero is only included if tmin <= 0 <= tmax
+#[allow(clippy::cast_possible_truncation)] // the sample offsets are rounded and a window will not span 2^63 samples
+fn window_times(tmin: f64, tmax: f64, srate: f64) -> Vec<f64> {
+    let first = (tmin * srate).round() as i64;
+    let last = (tmax * srate).round() as i64;
+
+    (first..=last).map(|k| k as f64 / srate).collect()
+}
+
+// subtracts the mean of each channel over the samples in `range` from a single epoch
+fn subtract_baseline(epoch: &mut [f64], channel_count: usize, range: std::ops::Range<usize>) {
+    let count = range.len() as f64;
+
+    for channel in 0..channel_count {
+        let mean = epoch[range.start * channel_count..range.end * channel_count]
+            .iter()
+            .skip(channel)
+            .step_by(channel_count)
+            .sum::<f64>()
+            / count;
+        for value in epoch.iter_mut().skip(channel).step_by(channel_count) {
+            *value -= mean;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Values;
+
+    const EPSILON: f64 = 1E-9;
+
+    // 100 Hz from 0 to 10 s, channel 0 is the time in ms and channel 1 a constant
+    fn eeg_stream() -> Stream {
+        let samples = (0..=1000).map(|i| (f64::from(i) / 100.0, Values::Int32(vec![i * 10, 7])));
+        Stream::from_test_timestamps(Format::Int32, 2, Some(100.0), samples)
+    }
+
+    fn event(time: f64) -> Event {
+        Event {
+            time,
+            label: "stimulus".to_string(),
+            stream_id: 1,
+            index: 0,
+        }
+    }
+
+    #[test]
+    fn test_epochs() {
+        let stream = eeg_stream();
+        let events = [event(0.1), event(5.003), event(9.9)];
+
+        let epochs = stream.epochs(&events, -0.2, 0.8, None).unwrap();
+
+        assert_eq!(epochs.times.len(), 101);
+        assert!((epochs.times[0] + 0.2).abs() < EPSILON);
+        assert!((epochs.times[100] - 0.8).abs() < EPSILON);
+
+        // the windows of the first and last event exceed the stream
+        assert_eq!(epochs.len(), 1);
+        assert!((epochs.events[0].time - 5.003).abs() < EPSILON);
+
+        assert_eq!(epochs.shape(), (1, 101, 2));
+        let epoch = epochs.epoch(0).unwrap();
+        assert_eq!(epoch.len(), 202);
+        assert_eq!(epoch[..2], [4800.0, 7.0]);
+        assert_eq!(epoch[200..], [5800.0, 7.0]);
+        assert_eq!(epochs.epoch(1), None);
+    }
+
+    #[test]
+    fn test_epochs_baseline() {
+        let stream = eeg_stream();
+
+        let epochs = stream.epochs(&[event(5.0)], -0.2, 0.8, Some((-0.2, 0.0))).unwrap();
+        let epoch = epochs.epoch(0).unwrap();
+
+        // the mean of the ramp from 4800 to 5000 is 4900
+        assert!((epoch[0] + 100.0).abs() < EPSILON);
+        assert!((epoch[40] - 100.0).abs() < EPSILON);
+        assert!(epoch.iter().skip(1).step_by(2).all(|value| value.abs() < EPSILON));
+    }
+
+    #[test]
+    fn test_epochs_across_gap() {
+        let mut stream = eeg_stream();
+        // remove the samples between 5 and 6 seconds
+        stream.samples.drain(501..600);
+
+        let epochs = stream.epochs(&[event(5.5), event(7.0)], -0.2, 0.8, None).unwrap();
+        assert_eq!(epochs.len(), 1);
+        assert!((epochs.events[0].time - 7.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_epochs_errors() {
+        let stream = eeg_stream();
+
+        assert!(matches!(
+            stream.epochs(&[], 0.8, -0.2, None),
+            Err(StreamError::InvalidWindow { .. })
+        ));
+        assert!(matches!(
+            stream.epochs(&[], -0.2, 0.8, Some((1.0, 2.0))),
+            Err(StreamError::InvalidWindow { .. })
+        ));
+
+        let mut irregular = eeg_stream();
+        irregular.nominal_srate = None;
+        assert!(matches!(
+            irregular.epochs(&[], -0.2, 0.8, None),
+            Err(StreamError::InvalidSrate(_))
+        ));
+    }
+}
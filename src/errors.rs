@@ -2,6 +2,10 @@
 use std::sync::Arc;
 use thiserror::Error;
 
+use crate::Format;
+
+/// The error type of this crate, wrapping the more specific errors below.
+#[allow(missing_docs)]
 #[derive(Debug, Error)]
 pub enum XDFError {
     #[error(transparent)]
@@ -17,6 +21,8 @@ pub enum XDFError {
     IO(#[from] std::io::Error),
 }
 
+/// Errors related to the XML parts of the file.
+#[allow(missing_docs)]
 #[derive(Debug, Error)]
 pub enum XMLError {
     #[error("The XML element either does not exist or contains invalid or no data: {0}")]
@@ -26,6 +32,8 @@ pub enum XMLError {
     ParseError(#[from] xmltree::ParseError),
 }
 
+/// Errors related to streams, either while assembling them from chunks or when working with a parsed [`crate::Stream`].
+#[allow(missing_docs)]
 #[derive(Debug, Error)]
 pub enum StreamError {
     #[error("Could not find stream header chunk for stream id {0}")]
@@ -34,6 +42,12 @@ pub enum StreamError {
     #[error("Could not find stream footer chunk for stream id {0}")]
     MissingFooter(u32),
 
+    #[error("There is no stream with {0}")]
+    StreamNotFound(String),
+
+    #[error("There are several streams with {query}: {ids:?}")]
+    AmbiguousStream { query: String, ids: Vec<u32> },
+
     #[error("Could not find file header chunk")]
     MissingFileHeader,
 
@@ -42,8 +56,34 @@ pub enum StreamError {
 
     #[error("Version {0} is not supported")]
     UnsupportedVersion(f32),
+
+    #[error("This operation is not supported for streams of format {0:?}")]
+    UnsupportedFormat(Format),
+
+    #[error("The stream has no channel with {0}")]
+    ChannelNotFound(String),
+
+    #[error("Expected {expected} channels but got {actual}")]
+    WrongChannelCount { expected: u32, actual: usize },
+
+    #[error("Invalid sampling rate: {0}")]
+    InvalidSrate(f64),
+
+    #[error("Requested values of format {requested:?} from a stream of format {actual:?}")]
+    WrongFormat { requested: Format, actual: Format },
+
+    #[error("Invalid time window from {start} to {end}")]
+    InvalidWindow { start: f64, end: f64 },
+
+    #[error("Got {timestamps} timestamps for {samples} samples")]
+    MismatchedTimestamps { samples: usize, timestamps: usize },
+
+    #[error("The values of a sample do not match the stream's format {0:?} or its channel count")]
+    MismatchedValues(Format),
 }
 
+/// Errors related to the binary structure of the file.
+#[allow(missing_docs)]
 #[derive(Debug, Error)]
 pub enum ParseError {
     #[error("Error parsing chunk")]
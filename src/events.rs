@@ -0,0 +1,139 @@
+use crate::{Stream, Values, XDFFile};
+
+/// A single marker of a string stream, for example a stimulus onset or a response.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Event {
+    /// The (clock corrected) timestamp of the marker.
+    pub time: f64,
+    /// The marker itself, i.e. the value of the first channel.
+    /// Further channels, such as a condition label, are available through the sample at `index`.
+    pub label: String,
+    /// The id of the stream the marker belongs to.
+    pub stream_id: u32,
+    /// The index of the marker's sample in [`Stream::samples`].
+    pub index: usize,
+}
+
+impl Stream {
+    /// Returns the markers of a string stream as events, in the order of the samples.
+    /// Samples without a timestamp or without values are skipped. Empty for numeric streams.
+    #[must_use]
+    pub fn events(&self) -> Vec<Event> {
+        self.samples
+            .iter()
+            .enumerate()
+            .filter_map(|(index, sample)| match (&sample.values, sample.timestamp) {
+                (Values::String(values), Some(time)) => Some(Event {
+                    time,
+                    label: values.first()?.clone(),
+                    stream_id: self.id,
+                    index,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl XDFFile {
+    /**
+    Merges the markers of all string streams into a single list of events sorted by time.
+    Simultaneous events keep the order of their streams in [`XDFFile::streams`].
+
+    # Example
+    ```rust
+    # use std::fs;
+    # use xdf::XDFFile;
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = fs::read("tests/minimal.xdf")?;
+    let xdf_file = XDFFile::from_bytes(&bytes)?;
+
+    let greetings: Vec<_> = xdf_file.events().into_iter().filter(|e| e.label == "Hello").collect();
+    assert_eq!(greetings.len(), 2);
+    # Ok(())
+    # }
+    ```
+    */
+    #[must_use]
+    pub fn events(&self) -> Vec<Event> {
+        let mut events: Vec<Event> = self.streams.iter().flat_map(Stream::events).collect();
+        events.sort_by(|a, b| a.time.total_cmp(&b.time));
+        events
+    }
+
+    /// Returns the events of all string streams whose label matches `pattern`, sorted by time. See [`XDFFile::events`].
+    /// Only available with the `regex` feature.
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn events_matching(&self, pattern: &regex::Regex) -> Vec<Event> {
+        let mut events = self.events();
+        events.retain(|event| pattern.is_match(&event.label));
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Format;
+
+    fn marker_stream(id: u32, markers: &[(Option<f64>, &str)]) -> Stream {
+        let samples = markers
+            .iter()
+            .map(|&(timestamp, label)| (timestamp, Values::String(vec![label.to_string()])));
+        let mut stream = Stream::from_test_timestamps(Format::String, 1, None, samples);
+        stream.id = id;
+        stream
+    }
+
+    fn xdf_file() -> XDFFile {
+        let numeric_stream =
+            Stream::from_test_timestamps(Format::Float32, 1, None, [(0.0, Values::Float32(vec![1.0]))]);
+
+        XDFFile::from_test_streams(vec![
+            marker_stream(
+                1,
+                &[(Some(1.0), "stimulus/left"), (None, "lost"), (Some(3.0), "response")],
+            ),
+            numeric_stream,
+            marker_stream(2, &[(Some(2.0), "stimulus/right"), (Some(3.0), "feedback")]),
+        ])
+    }
+
+    #[test]
+    fn test_stream_events() {
+        let xdf_file = xdf_file();
+
+        let events = xdf_file.streams[0].events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[1],
+            Event {
+                time: 3.0,
+                label: "response".to_string(),
+                stream_id: 1,
+                index: 2,
+            }
+        );
+
+        assert!(xdf_file.streams[1].events().is_empty());
+    }
+
+    #[test]
+    fn test_merged_events() {
+        let events = xdf_file().events();
+        let labels: Vec<&str> = events.iter().map(|e| e.label.as_str()).collect();
+
+        assert_eq!(labels, vec!["stimulus/left", "stimulus/right", "response", "feedback"]);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_events_matching() {
+        let pattern = regex::Regex::new("^stimulus/").unwrap();
+        let events = xdf_file().events_matching(&pattern);
+
+        assert_eq!(events.iter().map(|e| e.stream_id).collect::<Vec<_>>(), vec![1, 2]);
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset};
+use xmltree::Element;
+
+use crate::util::get_text_from_child;
+
+// the names under which recorders note themselves in the file header, in order of preference
+const RECORDER_FIELDS: [&str; 3] = ["recorder", "writer", "application"];
+
+/**
+Metadata about the recording from the file header, see [`crate::XDFFile::header`].
+
+`LabRecorder` writes the time the recording was started as `datetime`, other recorders may add further fields.
+Fields which are missing or empty are None.
+*/
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileInfo {
+    /// The local date and time at which the recording was started as written in the file, for example
+    /// `2019-10-17T16:10:38+0200`. With the `chrono` feature, `FileInfo::start_time` parses it.
+    pub datetime: Option<String>,
+    /// The name of the application which wrote the file, from `recorder`, `writer` or `application`.
+    pub recorder: Option<String>,
+    /// The text of all other child elements of the header except `version`, by name.
+    pub extra: HashMap<String, String>,
+}
+
+impl FileInfo {
+    pub(crate) fn from_header(header: &Element) -> Self {
+        let text = |name: &str| {
+            get_text_from_child(header, name)
+                .ok()
+                .map(|text| text.trim().to_string())
+                .filter(|text| !text.is_empty())
+        };
+
+        let extra = header
+            .children
+            .iter()
+            .filter_map(|node| node.as_element())
+            .filter(|element| element.name != "version" && element.name != "datetime")
+            .filter(|element| !RECORDER_FIELDS.contains(&element.name.as_str()))
+            .filter_map(|element| Some((element.name.clone(), element.get_text()?.into_owned())))
+            .collect();
+
+        Self {
+            datetime: text("datetime"),
+            recorder: RECORDER_FIELDS.iter().find_map(|&name| text(name)),
+            extra,
+        }
+    }
+
+    /// Returns the time at which the recording was started, including the offset from UTC.
+    /// Available with the `chrono` feature.
+    /// None if there is no `datetime` or it cannot be parsed, for example because it lacks the offset.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn start_time(&self) -> Option<DateTime<FixedOffset>> {
+        // LabRecorder writes ISO 8601 without a colon in the offset, for example 2019-10-17T16:10:38+0200
+        let datetime = self.datetime.as_deref()?;
+        DateTime::parse_from_rfc3339(datetime)
+            .or_else(|_| DateTime::parse_from_str(datetime, "%Y-%m-%dT%H:%M:%S%.f%z"))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "chrono")]
+    use chrono::{TimeZone, Timelike};
+
+    fn header(contents: &str) -> Element {
+        Element::parse(format!("<info><version>1.0</version>{contents}</info>").as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_file_info() {
+        let info = FileInfo::from_header(&header(
+            "<datetime>2019-10-17T16:10:38+0200</datetime><writer>LabRecorder</writer><site>lab 2</site>",
+        ));
+
+        assert_eq!(info.datetime.as_deref(), Some("2019-10-17T16:10:38+0200"));
+        assert_eq!(info.recorder.as_deref(), Some("LabRecorder"));
+        assert_eq!(info.extra.len(), 1);
+        assert_eq!(info.extra["site"], "lab 2");
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_start_time() {
+        let parsed = |text: &str| FileInfo::from_header(&header(&format!("<datetime>{text}</datetime>"))).start_time();
+
+        let expected = FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2019, 10, 17, 16, 10, 38)
+            .unwrap();
+        assert_eq!(parsed("2019-10-17T16:10:38+0200"), Some(expected));
+
+        let utc = parsed("2019-10-17T14:10:38.5Z").unwrap();
+        assert_eq!(utc.offset().local_minus_utc(), 0);
+        assert_eq!(utc.nanosecond(), 500_000_000);
+
+        assert_eq!(parsed("2019-10-17T16:10:38+02:00"), parsed("2019-10-17T16:10:38+0200"));
+
+        // without an offset the time cannot be placed
+        assert_eq!(parsed("2019-10-17T16:10:38"), None);
+        assert_eq!(parsed("yesterday"), None);
+    }
+
+    #[test]
+    fn test_file_info_empty() {
+        assert_eq!(FileInfo::from_header(&header("")), FileInfo::default());
+        assert_eq!(
+            FileInfo::from_header(&header("<datetime> </datetime>")),
+            FileInfo::default()
+        );
+    }
+}
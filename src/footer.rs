@@ -0,0 +1,239 @@
+use std::fmt::Display;
+
+use xmltree::Element;
+
+use crate::{util::parse_child, ClockOffset, Stream};
+
+/**
+The contents of a stream footer as written by `LabRecorder`.
+
+The timestamps are given in the clock of the stream, i.e. before clock offsets are applied.
+The clock offsets are a summary written by the recorder, the ones actually used are in [`Stream::clock_offsets`].
+Fields which are missing or cannot be parsed are None.
+*/
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamFooter {
+    /// The timestamp of the first sample.
+    pub first_timestamp: Option<f64>,
+    /// The timestamp of the last sample.
+    pub last_timestamp: Option<f64>,
+    /// The number of samples the recorder wrote.
+    pub sample_count: Option<u64>,
+    /// The clock offsets listed in the footer.
+    pub clock_offsets: Vec<ClockOffset>,
+}
+
+impl StreamFooter {
+    pub(crate) fn from_element(footer: &Element) -> Self {
+        let clock_offsets = footer
+            .get_child("clock_offsets")
+            .map(|offsets| {
+                offsets
+                    .children
+                    .iter()
+                    .filter_map(|node| node.as_element())
+                    .filter(|element| element.name == "offset")
+                    .filter_map(|offset| {
+                        Some(ClockOffset {
+                            collection_time: parse_child(offset, "time")?,
+                            offset_value: parse_child(offset, "value")?,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            first_timestamp: parse_child(footer, "first_timestamp"),
+            last_timestamp: parse_child(footer, "last_timestamp"),
+            sample_count: parse_child(footer, "sample_count"),
+            clock_offsets,
+        }
+    }
+}
+
+/// A difference between a stream's footer and its decoded samples, see [`Stream::verify_footer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FooterMismatch {
+    /// The number of decoded samples differs from the footer's sample count, for example because samples were lost.
+    SampleCount {
+        /// The sample count given in the footer.
+        footer: u64,
+        /// The number of decoded samples.
+        decoded: usize,
+    },
+    /// The timestamp of the first decoded sample differs from the footer's first timestamp.
+    FirstTimestamp {
+        /// The timestamp given in the footer.
+        footer: f64,
+        /// The timestamp of the first decoded sample without clock correction, None if it has no timestamp.
+        decoded: Option<f64>,
+    },
+    /// The timestamp of the last decoded sample differs from the footer's last timestamp.
+    LastTimestamp {
+        /// The timestamp given in the footer.
+        footer: f64,
+        /// The timestamp of the last decoded sample without clock correction, None if it has no timestamp.
+        decoded: Option<f64>,
+    },
+}
+
+impl Display for FooterMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SampleCount { footer, decoded } => {
+                write!(f, "the footer lists {footer} samples but {decoded} were decoded")
+            }
+            Self::FirstTimestamp { footer, decoded } => {
+                write!(
+                    f,
+                    "the footer's first timestamp is {footer} but the first sample's is {decoded:?}"
+                )
+            }
+            Self::LastTimestamp { footer, decoded } => {
+                write!(
+                    f,
+                    "the footer's last timestamp is {footer} but the last sample's is {decoded:?}"
+                )
+            }
+        }
+    }
+}
+
+impl Stream {
+    /**
+    Cross-checks the decoded samples against the stream's footer to spot recordings where samples were lost.
+
+    The footer's timestamps are compared to the timestamps of the first and last sample with the clock correction
+    and any rebasing undone. Since the correction is undone using the corrected timestamp, this is only approximate
+    for quickly drifting clocks, which is what `tolerance` (in seconds) is for.
+    Half a sample period is a reasonable choice for regularly sampled streams.
+
+    # Returns
+    All mismatches that were found. Empty if everything matches or the stream has no footer.
+    */
+    #[must_use]
+    pub fn verify_footer(&self, tolerance: f64) -> Vec<FooterMismatch> {
+        let Some(footer) = &self.footer_info else {
+            return Vec::new();
+        };
+
+        let mut mismatches = Vec::new();
+
+        if let Some(count) = footer.sample_count {
+            if usize::try_from(count).ok() != Some(self.samples.len()) {
+                mismatches.push(FooterMismatch::SampleCount {
+                    footer: count,
+                    decoded: self.samples.len(),
+                });
+            }
+        }
+
+        let uncorrected = |timestamp: f64| {
+            let timestamp = timestamp + self.time_origin;
+            timestamp - self.clock_model.offset_at(timestamp)
+        };
+        let matches =
+            |expected: f64, decoded: Option<f64>| decoded.is_some_and(|ts| (ts - expected).abs() <= tolerance);
+
+        if let Some(expected) = footer.first_timestamp {
+            let decoded = self.samples.first().and_then(|s| s.timestamp).map(uncorrected);
+            if !matches(expected, decoded) {
+                mismatches.push(FooterMismatch::FirstTimestamp {
+                    footer: expected,
+                    decoded,
+                });
+            }
+        }
+
+        if let Some(expected) = footer.last_timestamp {
+            let decoded = self.samples.last().and_then(|s| s.timestamp).map(uncorrected);
+            if !matches(expected, decoded) {
+                mismatches.push(FooterMismatch::LastTimestamp {
+                    footer: expected,
+                    decoded,
+                });
+            }
+        }
+
+        mismatches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClockModel, Format, Values};
+
+    const EPSILON: f64 = 1E-9;
+
+    fn footer() -> Element {
+        Element::parse(
+            r"<info>
+                <writer>LabRecorder xdfwriter</writer>
+                <first_timestamp>5.1</first_timestamp>
+                <last_timestamp>5.3</last_timestamp>
+                <sample_count>3</sample_count>
+                <clock_offsets>
+                    <offset><time>50979.76</time><value>-.01</value></offset>
+                    <offset><time>50979.86</time><value>-.02</value></offset>
+                </clock_offsets>
+            </info>"
+                .as_bytes(),
+        )
+        .unwrap()
+    }
+
+    fn stream_with_footer(timestamps: &[f64]) -> Stream {
+        let samples = timestamps.iter().map(|&ts| (ts, Values::Int8(vec![0])));
+        let mut stream = Stream::from_test_timestamps(Format::Int8, 1, Some(10.0), samples);
+        stream.footer_info = Some(StreamFooter::from_element(&footer()));
+        stream
+    }
+
+    #[test]
+    fn test_parse_footer() {
+        let footer = StreamFooter::from_element(&footer());
+
+        assert_eq!(footer.first_timestamp, Some(5.1));
+        assert_eq!(footer.last_timestamp, Some(5.3));
+        assert_eq!(footer.sample_count, Some(3));
+        assert_eq!(footer.clock_offsets.len(), 2);
+        assert!((footer.clock_offsets[1].collection_time - 50_979.86).abs() < EPSILON);
+        assert!((footer.clock_offsets[1].offset_value + 0.02).abs() < EPSILON);
+
+        let empty = StreamFooter::from_element(&Element::new("info"));
+        assert_eq!(empty, StreamFooter::default());
+    }
+
+    #[test]
+    fn test_verify_footer() {
+        let stream = stream_with_footer(&[5.1, 5.2, 5.3]);
+        assert!(stream.verify_footer(0.05).is_empty());
+
+        // with a constant offset of -0.1 the corrected timestamps are 0.1 earlier
+        let mut stream = stream_with_footer(&[5.0, 5.1, 5.2]);
+        stream.clock_model = ClockModel::Linear {
+            intercept: -0.1,
+            slope: 0.0,
+        };
+        assert!(stream.verify_footer(0.05).is_empty());
+    }
+
+    #[test]
+    fn test_verify_footer_lost_samples() {
+        let stream = stream_with_footer(&[5.1, 5.2]);
+
+        assert_eq!(
+            stream.verify_footer(0.05),
+            vec![
+                FooterMismatch::SampleCount { footer: 3, decoded: 2 },
+                FooterMismatch::LastTimestamp {
+                    footer: 5.3,
+                    decoded: Some(5.2)
+                },
+            ]
+        );
+    }
+}
@@ -32,25 +32,96 @@
 //!# Ok(())
 //!# }
 //!```
+//!
+//! # Features
+//! * `arrow`: conversion of streams into Arrow record batches, see `Stream::to_record_batch`.
+//! * `chrono`: the recording start time as a [`chrono`](https://docs.rs/chrono) date-time, see `FileInfo::start_time`.
+//! * `ndarray`: conversions between streams and [`ndarray`](https://docs.rs/ndarray) arrays.
+//! * `regex`: filtering events by their label with a regular expression, see `XDFFile::events_matching`.
+//! * `serde`: `Serialize` and `Deserialize` for [`XDFFile`], [`Stream`] and related types. XML elements are (de)serialised as strings.
 
 use std::collections::HashMap;
 
 use std::iter::Iterator;
 use std::sync::Arc;
 
+mod align;
+pub use align::Lookup;
+
+#[cfg(feature = "ndarray")]
+mod arrays;
+
 mod chunk_structs;
+pub use chunk_structs::UnknownChunk;
+
+mod channels;
+pub use channels::ChannelInfo;
+
+mod columnar;
+pub use columnar::{ColumnarStream, Columns};
+
+mod epochs;
+pub use epochs::Epochs;
+
+mod events;
+pub use events::Event;
+
+mod file_info;
+pub use file_info::FileInfo;
+
+mod footer;
+pub use footer::{FooterMismatch, StreamFooter};
+
 mod errors;
+pub use errors::{ParseError, StreamError, XDFError, XMLError};
+
+mod clock_sync;
+pub use clock_sync::{
+    ClockModel, ClockOffset, ClockSync, LeastSquaresSync, NearestSync, NoSync, PiecewiseLinearSync, StepSync,
+};
+
+mod options;
+pub use options::{ParseOptions, StreamOrder};
 
 mod sample;
 pub use sample::Sample;
 
+mod query;
+
+mod resample;
+pub use resample::ResampleMethod;
+
+#[cfg(feature = "arrow")]
+mod record_batch;
+
+mod scalar;
+pub use scalar::XdfScalar;
+
+mod slicing;
+pub use slicing::ChannelSelector;
+
+mod scaling;
+pub use scaling::Scaling;
+
+mod segments;
+pub use segments::Segment;
+
 mod streams;
+pub use streams::Stream;
+
+mod stream_info;
+pub use stream_info::StreamInfo;
+
+mod time_base;
+pub use time_base::TimeOrigin;
+
 mod util;
 
+#[cfg(feature = "serde")]
+mod xml_serde;
+
 use chunk_structs::{BoundaryChunk, ClockOffsetChunk, FileHeaderChunk, StreamFooterChunk, StreamHeaderChunk};
-use errors::{ParseError, StreamError, XDFError};
 use log::warn;
-use streams::Stream;
 use util::FiniteF64;
 
 use crate::chunk_structs::Chunk;
@@ -59,22 +130,32 @@ mod parsers;
 use crate::parsers::xdf_file::xdf_file_parser;
 
 type StreamID = u32;
-type SampleIter = std::vec::IntoIter<Sample>;
+type SampleIter = chunk_structs::ChunkSamples;
 
 /// XDF file struct
 /// The main struct representing an XDF file.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct XDFFile {
     /// XDF version. Currently only 1.0 exists according to the specification.
     pub version: f32,
     /// The XML header of the XDF file as an [`xmltree::Element`].
+    #[cfg_attr(feature = "serde", serde(with = "crate::xml_serde"))]
     pub header: xmltree::Element,
+    /// The recording metadata from [`XDFFile::header`], such as the time the recording was started.
+    pub info: FileInfo,
     /// A vector of streams contained in the XDF file.
     pub streams: Vec<Stream>,
+    /// Chunks with tags not defined by the specification, in the order they appear in the file.
+    pub unknown_chunks: Vec<UnknownChunk>,
+    /// The time in the clock of the recording computer which the timestamps of all streams are relative to.
+    /// This is zero unless the streams were rebased, see [`TimeOrigin`].
+    pub time_origin: f64,
 }
 
 /// Possible formats for the data in a stream as given in the specification.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Format {
     /// signed 8-bit integer
     Int8,
@@ -92,9 +173,25 @@ pub enum Format {
     String,
 }
 
-/// The values of a sample in a stream. The values are stored as a vector of the corresponding type (or a string).
+impl Format {
+    // the name of the format as used in the channel_format field of stream headers
+    pub(crate) const fn as_xdf_str(self) -> &'static str {
+        match self {
+            Self::Int8 => "int8",
+            Self::Int16 => "int16",
+            Self::Int32 => "int32",
+            Self::Int64 => "int64",
+            Self::Float32 => "float32",
+            Self::Float64 => "double64",
+            Self::String => "string",
+        }
+    }
+}
+
+/// The values of a sample in a stream. The values are stored as a vector of the corresponding type, with one entry per channel.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Values {
     Int8(Vec<i8>),
     Int16(Vec<i16>),
@@ -102,7 +199,58 @@ pub enum Values {
     Int64(Vec<i64>),
     Float32(Vec<f32>),
     Float64(Vec<f64>),
-    String(String),
+    String(Vec<String>),
+}
+
+impl Values {
+    /// The number of values, i.e. the number of channels.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Int8(v) => v.len(),
+            Self::Int16(v) => v.len(),
+            Self::Int32(v) => v.len(),
+            Self::Int64(v) => v.len(),
+            Self::Float32(v) => v.len(),
+            Self::Float64(v) => v.len(),
+            Self::String(v) => v.len(),
+        }
+    }
+
+    /// Returns true if there are no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The [`Format`] corresponding to these values.
+    #[must_use]
+    pub const fn format(&self) -> Format {
+        match self {
+            Self::Int8(_) => Format::Int8,
+            Self::Int16(_) => Format::Int16,
+            Self::Int32(_) => Format::Int32,
+            Self::Int64(_) => Format::Int64,
+            Self::Float32(_) => Format::Float32,
+            Self::Float64(_) => Format::Float64,
+            Self::String(_) => Format::String,
+        }
+    }
+
+    // converts numeric values to f64, for the numeric processing in this crate. None for strings.
+    // Note that this is lossy for very large 64-bit integers.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn to_f64_vec(&self) -> Option<Vec<f64>> {
+        match self {
+            Self::Int8(v) => Some(v.iter().map(|&x| f64::from(x)).collect()),
+            Self::Int16(v) => Some(v.iter().map(|&x| f64::from(x)).collect()),
+            Self::Int32(v) => Some(v.iter().map(|&x| f64::from(x)).collect()),
+            Self::Int64(v) => Some(v.iter().map(|&x| x as f64).collect()),
+            Self::Float32(v) => Some(v.iter().map(|&x| f64::from(x)).collect()),
+            Self::Float64(v) => Some(v.clone()),
+            Self::String(_) => None,
+        }
+    }
 }
 
 struct GroupedChunks {
@@ -110,6 +258,7 @@ struct GroupedChunks {
     stream_footer_chunks: Vec<StreamFooterChunk>,
     clock_offsets: HashMap<StreamID, Vec<ClockOffsetChunk>>,
     sample_map: HashMap<StreamID, Vec<SampleIter>>,
+    unknown_chunks: Vec<UnknownChunk>,
 }
 
 impl XDFFile {
@@ -133,6 +282,32 @@ impl XDFFile {
     ```
     */
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, XDFError> {
+        Self::from_bytes_with_options(bytes, &ParseOptions::default())
+    }
+
+    /**
+    Parse an XDF file from a byte slice, using the given [`ParseOptions`].
+    # Arguments
+    * `bytes` - A byte slice of the whole XDF file as read from disk.
+    * `options` - Options controlling how the streams are processed, for example the clock synchronisation strategy.
+    # Errors
+    See [`XDFFile::from_bytes`].
+    # Example
+    ```rust
+    # use std::{fs, sync::Arc};
+    # use xdf::{LeastSquaresSync, ParseOptions, XDFFile};
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = fs::read("tests/minimal.xdf")?;
+    let options = ParseOptions {
+        clock_sync: Arc::new(LeastSquaresSync),
+        ..Default::default()
+    };
+    let xdf_file = XDFFile::from_bytes_with_options(&bytes, &options)?;
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn from_bytes_with_options(bytes: &[u8], options: &ParseOptions) -> Result<Self, XDFError> {
         // this error mapping could use some simplification
         let (input, chunks) = xdf_file_parser(bytes)
             .map_err(|e| match e {
@@ -154,15 +329,49 @@ impl XDFFile {
             warn!("There are {} bytes left in the input after parsing.", input.len());
         }
 
-        let (file_header_chunk, grouped_chunks) = group_chunks(chunks)?;
+        let (file_header_chunk, mut grouped_chunks) = group_chunks(chunks)?;
+        let unknown_chunks = std::mem::take(&mut grouped_chunks.unknown_chunks);
 
-        let streams = process_streams(grouped_chunks)?;
+        let streams = process_streams(grouped_chunks, options)?;
 
-        Ok(Self {
+        let mut xdf_file = Self {
             version: file_header_chunk.version,
+            info: FileInfo::from_header(&file_header_chunk.xml),
             header: file_header_chunk.xml,
             streams,
-        })
+            unknown_chunks,
+            time_origin: 0.0,
+        };
+
+        match options.stream_order {
+            StreamOrder::File => {}
+            StreamOrder::Id => xdf_file.streams.sort_by_key(|s| s.id),
+            StreamOrder::Name => xdf_file
+                .streams
+                .sort_by(|a, b| (a.name.is_none(), &a.name, a.id).cmp(&(b.name.is_none(), &b.name, b.id))),
+        }
+
+        match options.time_origin {
+            TimeOrigin::Recorder => {}
+            TimeOrigin::FirstSample => xdf_file.rebase_to_first_sample(),
+        }
+
+        Ok(xdf_file)
+    }
+}
+
+#[cfg(test)]
+impl XDFFile {
+    // builds a version 1.0 file with an empty header around the given streams. Only meant for unit tests.
+    pub(crate) fn from_test_streams(streams: Vec<Stream>) -> Self {
+        Self {
+            version: 1.0,
+            header: xmltree::Element::new("info"),
+            info: FileInfo::default(),
+            streams,
+            unknown_chunks: Vec::new(),
+            time_origin: 0.0,
+        }
     }
 }
 
@@ -172,6 +381,7 @@ fn group_chunks(chunks: Vec<Chunk>) -> Result<(FileHeaderChunk, GroupedChunks),
     let mut stream_header_chunks: Vec<StreamHeaderChunk> = Vec::new();
     let mut stream_footer_chunks: Vec<StreamFooterChunk> = Vec::new();
     let mut clock_offsets: HashMap<StreamID, Vec<ClockOffsetChunk>> = HashMap::new();
+    let mut unknown_chunks: Vec<UnknownChunk> = Vec::new();
 
     // the sample_map maps stream IDs to a vector of iterators which each iterate over one chunk's samples
     let sample_map = chunks
@@ -197,13 +407,18 @@ fn group_chunks(chunks: Vec<Chunk>) -> Result<(FileHeaderChunk, GroupedChunks),
                     None
                 }
                 Chunk::Boundary(_) => None, // boundary chunks are discarded for now
+                Chunk::Unknown(c) => {
+                    warn!("Skipping chunk with unknown tag {} at byte {}", c.tag, c.offset);
+                    unknown_chunks.push(c);
+                    None
+                }
             }
         })
         .fold(
             // fold the samples into a map of stream IDs to a vector of iterators so we can merge them later
             HashMap::new(),
             |mut map: HashMap<StreamID, Vec<SampleIter>>, chunk| {
-                map.entry(chunk.stream_id).or_default().push(chunk.samples.into_iter());
+                map.entry(chunk.stream_id).or_default().push(chunk.into_iter());
                 map
             },
         );
@@ -215,6 +430,7 @@ fn group_chunks(chunks: Vec<Chunk>) -> Result<(FileHeaderChunk, GroupedChunks),
         stream_footer_chunks,
         clock_offsets,
         sample_map,
+        unknown_chunks,
     };
 
     // yes I return these separately. It saves me a clone. Sue me.
@@ -222,8 +438,16 @@ fn group_chunks(chunks: Vec<Chunk>) -> Result<(FileHeaderChunk, GroupedChunks),
 }
 
 // takes grouped chunks and combines them into finished streams.
-fn process_streams(mut grouped_chunks: GroupedChunks) -> Result<Vec<Stream>, XDFError> {
-    let stream_header_map: HashMap<StreamID, StreamHeaderChunk> = grouped_chunks
+fn process_streams(mut grouped_chunks: GroupedChunks, options: &ParseOptions) -> Result<Vec<Stream>, XDFError> {
+    // the ids in the order of their first header in the file, which is the order of the resulting streams
+    let mut stream_ids: Vec<StreamID> = Vec::new();
+    for header in &grouped_chunks.stream_header_chunks {
+        if !stream_ids.contains(&header.stream_id) {
+            stream_ids.push(header.stream_id);
+        }
+    }
+
+    let mut stream_header_map: HashMap<StreamID, StreamHeaderChunk> = grouped_chunks
         .stream_header_chunks
         .into_iter()
         .map(|s| (s.stream_id, s))
@@ -237,8 +461,8 @@ fn process_streams(mut grouped_chunks: GroupedChunks) -> Result<Vec<Stream>, XDF
 
     // this can happen if the recording stops unexpectedly.
     // We allow this to be more error tolerant and not lose all experimental data.
-    for &stream_id in stream_header_map.keys() {
-        if !stream_footer_map.contains_key(&stream_id) {
+    for stream_id in &stream_ids {
+        if !stream_footer_map.contains_key(stream_id) {
             warn!("Stream header without corresponding stream footer for id: {stream_id}");
         }
     }
@@ -252,7 +476,10 @@ fn process_streams(mut grouped_chunks: GroupedChunks) -> Result<Vec<Stream>, XDF
 
     let mut streams_vec: Vec<Stream> = Vec::new();
 
-    for (stream_id, stream_header) in stream_header_map {
+    for stream_id in stream_ids {
+        let Some(stream_header) = stream_header_map.remove(&stream_id) else {
+            continue;
+        };
         let stream_footer = stream_footer_map.remove(&stream_id);
 
         let name = stream_header.info.name.as_ref().map(|name| Arc::from(name.as_str()));
@@ -275,34 +502,28 @@ fn process_streams(mut grouped_chunks: GroupedChunks) -> Result<Vec<Stream>, XDF
             return Err(ParseError::InvalidClockOffset.into());
         }
 
-        let samples_vec: Vec<Sample> = process_samples(
+        let clock_offsets: Vec<ClockOffset> = stream_offsets.into_iter().map(ClockOffset::from).collect();
+
+        // irregularly sampled streams such as markers are corrected just the same
+        let clock_model = options.clock_sync.fit(stream_id, &clock_offsets);
+
+        let (samples_vec, recorded_timestamps) = process_samples(
             grouped_chunks.sample_map.remove(&stream_id).unwrap_or_default(),
-            &stream_offsets,
+            &clock_model,
             stream_header.info.nominal_srate,
         );
 
-        let measured_srate = if stream_header.info.nominal_srate.is_some() {
-            // nominal_srate is given as "a floating point number in Hertz. If the stream
-            // has an irregular sampling rate (that is, the samples are not spaced evenly in
-            // time, for example in an event stream), this value must be 0."
-            // we use None instead of 0.
+        let measured_srate = measured_srate(&samples_vec, stream_header.info.nominal_srate);
 
-            let first_timestamp: Option<f64> = samples_vec.first().and_then(|s| s.timestamp);
-            let last_timestamp: Option<f64> = samples_vec.last().and_then(|s| s.timestamp);
-
-            if let (Some(first_timestamp), Some(last_timestamp)) = (first_timestamp, last_timestamp) {
-                let delta = last_timestamp - first_timestamp;
-                if delta <= 0.0 || !delta.is_finite() {
-                    None // don't divide by zero :)
-                } else {
-                    Some(samples_vec.len() as f64 / delta)
-                }
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let channels = channels::parse_channels(&stream_header.xml);
+        if !channels.is_empty() && channels.len() != stream_header.info.channel_count as usize {
+            warn!(
+                "Stream {} describes {} channels in its header but has a channel count of {}",
+                stream_id,
+                channels.len(),
+                stream_header.info.channel_count
+            );
+        }
 
         let stream = Stream {
             id: stream_id,
@@ -312,29 +533,67 @@ fn process_streams(mut grouped_chunks: GroupedChunks) -> Result<Vec<Stream>, XDF
 
             name,
             r#type: stream_type,
+            info: StreamInfo::from_header(&stream_header.xml),
             header: stream_header.xml,
+            footer_info: stream_footer.as_ref().map(|s| StreamFooter::from_element(&s.xml)),
             footer: stream_footer.map(|s| s.xml),
+            channels,
             measured_srate,
             samples: samples_vec,
+            recorded_timestamps,
+            clock_offsets,
+            clock_model,
+            time_origin: 0.0,
         };
 
+        // half a sample period for regular streams, a millisecond for irregular ones to absorb rounding
+        let tolerance = stream.nominal_srate.map_or(1E-3, |srate| 0.5 / srate);
+        for mismatch in stream.verify_footer(tolerance) {
+            warn!("Stream {stream_id}: {mismatch}");
+        }
+
         streams_vec.push(stream);
     }
 
     Ok(streams_vec)
 }
 
-/// takes a bunch of iterators over a stream's samples and some offsets and
-/// combines them into a vector of samples with timestamps corrected by interpolated clock offsets.
+/// calculates the sampling rate from the timestamps of the first and last sample.
+/// None for streams without a nominal sampling rate or without a usable time span.
+pub(crate) fn measured_srate(samples: &[Sample], nominal_srate: Option<f64>) -> Option<f64> {
+    // nominal_srate is given as "a floating point number in Hertz. If the stream
+    // has an irregular sampling rate (that is, the samples are not spaced evenly in
+    // time, for example in an event stream), this value must be 0."
+    // we use None instead of 0.
+    nominal_srate?;
+
+    let first_timestamp: Option<f64> = samples.first().and_then(|s| s.timestamp);
+    let last_timestamp: Option<f64> = samples.last().and_then(|s| s.timestamp);
+
+    srate_between(samples.len(), first_timestamp?, last_timestamp?)
+}
+
+/// the sampling rate of `count` evenly spaced samples from `first_timestamp` to `last_timestamp`, i.e. the number
+/// of sampling periods divided by the time span. Used for both [`Stream::measured_srate`] and
+/// [`Segment::effective_srate`] so that they agree.
+/// None if there are fewer than two samples or the time span is not positive.
+pub(crate) fn srate_between(count: usize, first_timestamp: f64, last_timestamp: f64) -> Option<f64> {
+    let delta = last_timestamp - first_timestamp;
+    if count < 2 || delta <= 0.0 || !delta.is_finite() {
+        None // don't divide by zero :)
+    } else {
+        Some((count - 1) as f64 / delta)
+    }
+}
+
+/// takes a bunch of iterators over a stream's samples and a clock model and
+/// combines them into a vector of samples with timestamps corrected by the model.
+/// Also returns the indices of the samples whose timestamps were read from the file rather than calculated.
 fn process_samples(
-    mut sample_iterators: Vec<SampleIter>,
-    stream_offsets: &[ClockOffsetChunk],
+    sample_iterators: Vec<SampleIter>,
+    clock_model: &ClockModel,
     nominal_srate: Option<f64>,
-) -> Vec<Sample> {
-    debug_assert!(stream_offsets
-        .iter()
-        .all(|o| o.stream_id == stream_offsets[0].stream_id));
-
+) -> (Vec<Sample>, Vec<usize>) {
     let mut offset_index: usize = 0;
 
     let mut most_recent_timestamp = (0_usize, 0_f64);
@@ -344,8 +603,10 @@ fn process_samples(
     // What if the first sample from the first iterator also has no timestamp?
     // Both the Python and the Matlab implementations use zero as a first default, so I've done the same here.
 
+    // the iterators are moved rather than borrowed so that each chunk's buffer is freed once its samples are taken
     let mut sample_iterators_merged = vec![];
-    if let Some((first, rest)) = sample_iterators.split_first_mut() {
+    let mut sample_iterators = sample_iterators.into_iter();
+    if let Some(first) = sample_iterators.next() {
         // We store each set of iterators with the first iter's first timestamp in a tuple
         let mut first = first.peekable();
         let first_ts = first
@@ -355,7 +616,7 @@ fn process_samples(
             .unwrap_or(FiniteF64::zero());
         sample_iterators_merged.push((first_ts, vec![first]));
 
-        for it in rest {
+        for it in sample_iterators {
             let mut it = it.peekable();
             if let Some(first_sample) = it.peek() {
                 // If there is a timestamp and it is finite, create a new set of iterators
@@ -391,13 +652,19 @@ fn process_samples(
     //     return Err(XDFError::InvalidSample);
     // }
 
-    sample_iterators
+    let mut recorded_timestamps = Vec::new();
+
+    let samples = sample_iterators
         .into_iter()
         .flatten()
         .enumerate()
         .map(|(i, s)| -> Sample {
-            if let Some(srate) = nominal_srate {
-                let timestamp = if let Some(timestamp) = s.timestamp {
+            if s.timestamp.is_some() {
+                recorded_timestamps.push(i);
+            }
+
+            let timestamp = if let Some(srate) = nominal_srate {
+                if let Some(timestamp) = s.timestamp {
                     // if the sample has its own timestamp, use that and update the most recent timestamp
                     most_recent_timestamp = (i, timestamp);
                     s.timestamp
@@ -406,31 +673,33 @@ fn process_samples(
                     let (old_i, old_timestamp) = most_recent_timestamp;
                     let samples_since_ts = i - old_i;
                     Some(old_timestamp + (samples_since_ts as f64 / srate))
-                };
-
-                let timestamp = timestamp.map(|ts| interpolate_and_add_offsets(ts, stream_offsets, &mut offset_index));
-
-                Sample {
-                    timestamp,
-                    values: s.values,
                 }
             } else {
-                s
+                s.timestamp
+            };
+
+            let timestamp = timestamp.map(|ts| ts + clock_model.offset_from_index(ts, &mut offset_index));
+
+            Sample {
+                timestamp,
+                values: s.values,
             }
         })
-        .collect()
+        .collect();
+
+    (samples, recorded_timestamps)
 }
 
 /// takes a timestamp and a vector of clock offsets and interpolates the offsets to find an offset for the timestamp.
 /// the `offset_index` is used to keep track where to start looking for the right clock offsets.
-fn interpolate_and_add_offsets(ts: f64, stream_offsets: &[ClockOffsetChunk], offset_index: &mut usize) -> f64 {
+pub(crate) fn interpolate_offset(ts: f64, stream_offsets: &[ClockOffset], offset_index: &mut usize) -> f64 {
     if stream_offsets.is_empty() {
-        ts //there are no offsets;
+        0.0 //there are no offsets;
     } else {
         let time_or_nan = |i: usize| {
             stream_offsets
                 .get(i + 1)
-                .map_or(f64::NAN, |c: &ClockOffsetChunk| c.collection_time)
+                .map_or(f64::NAN, |c: &ClockOffset| c.collection_time)
             //use NaN to break out of the loop below in case we've gone out of bounds
             // this avoids an infinite loop in the unusual case where all clock offsets are newer than the timestamp.
         };
@@ -448,7 +717,7 @@ fn interpolate_and_add_offsets(ts: f64, stream_offsets: &[ClockOffsetChunk], off
             // I initially thought this would be an invalid state, however this can happen if the chunks are not in order of collection time.
             // This isn't great but not fatal either. We check clock offsets for being in order, so it can't be those.
             // As a best effort we add the first stream offset, as that is the closest one.
-            return ts + stream_offsets[0].offset_value;
+            return stream_offsets[0].offset_value;
         }
 
         // ensure clock offset at offset_index is older than the current timestamp
@@ -462,7 +731,7 @@ fn interpolate_and_add_offsets(ts: f64, stream_offsets: &[ClockOffsetChunk], off
         // and the clock offset which comes next
         let next_offset = stream_offsets.get(*offset_index + 1).or_else(|| stream_offsets.last());
 
-        let interpolated = if let (Some(l), Some(n)) = (prev_offset, next_offset) {
+        if let (Some(l), Some(n)) = (prev_offset, next_offset) {
             // nearly all cases will have to be interpolated
             // a * (1-x) + b * x (with x between 0 and 1 of course)
 
@@ -477,9 +746,7 @@ fn interpolate_and_add_offsets(ts: f64, stream_offsets: &[ClockOffsetChunk], off
             }
         } else {
             prev_offset.or(next_offset).map_or(0.0, |c| c.offset_value)
-        };
-
-        ts + interpolated
+        }
     }
 }
 
@@ -489,6 +756,8 @@ fn interpolate_and_add_offsets(ts: f64, stream_offsets: &[ClockOffsetChunk], off
 mod tests {
 
     use super::*;
+    use crate::chunk_structs::{ChunkValues, SamplesChunk, StreamHeaderChunkInfo};
+    use xmltree::Element;
 
     const EPSILON: f64 = 1E-14;
 
@@ -496,15 +765,13 @@ mod tests {
     #[test]
     fn test_interpolation_bad_offset() {
         let offsets = vec![
-            ClockOffsetChunk {
+            ClockOffset {
                 collection_time: 0.0,
                 offset_value: -1.0,
-                stream_id: 0,
             },
-            ClockOffsetChunk {
+            ClockOffset {
                 collection_time: 1.0,
                 offset_value: 1.0,
-                stream_id: 0,
             },
         ];
         // after the range we expect for the last offset to be used
@@ -513,7 +780,7 @@ mod tests {
         let mut offset_index = 1;
 
         // should panic
-        interpolate_and_add_offsets(timestamp, &offsets, &mut offset_index);
+        interpolate_offset(timestamp, &offsets, &mut offset_index);
     }
 
     // test the interpolation function for timestamps *inside* the range of offsets
@@ -528,15 +795,13 @@ mod tests {
 
         for ((s1_t, s1_v), (s2_t, s2_v)) in TEST_VALUES {
             let offsets = vec![
-                ClockOffsetChunk {
+                ClockOffset {
                     collection_time: s1_t,
                     offset_value: s1_v,
-                    stream_id: 0,
                 },
-                ClockOffsetChunk {
+                ClockOffset {
                     collection_time: s2_t,
                     offset_value: s2_v,
-                    stream_id: 0,
                 },
             ];
 
@@ -557,7 +822,7 @@ mod tests {
             // test at multiple steps
             for timestamp in linspace(s1_t, s2_t, 100) {
                 let mut offset_index = 0;
-                let interpolated = interpolate_and_add_offsets(timestamp, &offsets, &mut offset_index);
+                let interpolated = timestamp + interpolate_offset(timestamp, &offsets, &mut offset_index);
 
                 let expected: f64 = timestamp + ((timestamp - first_pos.0) * incline + first_pos.1); // original timestamp + interpolated offset
 
@@ -573,27 +838,24 @@ mod tests {
     #[test]
     fn test_interpolation_after() {
         let offsets = vec![
-            ClockOffsetChunk {
+            ClockOffset {
                 collection_time: 0.0,
                 offset_value: -1.0,
-                stream_id: 0,
             },
-            ClockOffsetChunk {
+            ClockOffset {
                 collection_time: 1.0,
                 offset_value: 1.0,
-                stream_id: 0,
             },
-            ClockOffsetChunk {
+            ClockOffset {
                 collection_time: 3.0,
                 offset_value: 2.0,
-                stream_id: 0,
             },
         ];
         // after the range we expect for the last offset to be used
         let last_offset = offsets.last().unwrap();
         let timestamp = last_offset.collection_time + 1.0;
         let mut offset_index = 0;
-        let interpolated = interpolate_and_add_offsets(timestamp, &offsets, &mut offset_index);
+        let interpolated = timestamp + interpolate_offset(timestamp, &offsets, &mut offset_index);
         let expected = timestamp + last_offset.offset_value;
 
         assert!(
@@ -606,22 +868,20 @@ mod tests {
     #[test]
     fn test_interpolation_before() {
         let offsets = vec![
-            ClockOffsetChunk {
+            ClockOffset {
                 collection_time: 0.0,
                 offset_value: -1.0,
-                stream_id: 0,
             },
-            ClockOffsetChunk {
+            ClockOffset {
                 collection_time: 1.0,
                 offset_value: 1.0,
-                stream_id: 0,
             },
         ];
         // after the range we expect for the last offset to be used
         let first_offset = offsets.first().unwrap();
         let timestamp = first_offset.collection_time - 1.0;
         let mut offset_index = 0;
-        let interpolated = interpolate_and_add_offsets(timestamp, &offsets, &mut offset_index);
+        let interpolated = timestamp + interpolate_offset(timestamp, &offsets, &mut offset_index);
         let expected = timestamp + first_offset.offset_value;
 
         assert!(
@@ -638,13 +898,113 @@ mod tests {
 
         for i in -20..=20 {
             let timestamp = f64::from(i) / 10.0;
-            let res = interpolate_and_add_offsets(timestamp, &offsets, &mut offset_index);
+            let res = timestamp + interpolate_offset(timestamp, &offsets, &mut offset_index);
 
             //should be unchanged
             assert_eq!(timestamp, res);
         }
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_irregular_stream_corrected() {
+        let xml = Element::parse(
+            "<info><channel_count>1</channel_count><nominal_srate>0</nominal_srate><channel_format>string</channel_format></info>"
+                .as_bytes(),
+        )
+        .unwrap();
+        let samples = SamplesChunk {
+            stream_id: 3,
+            num_channels: 1,
+            timestamps: vec![Some(0.0), Some(0.5), Some(2.0)],
+            values: ChunkValues::String(vec!["marker".to_string(); 3]),
+        };
+
+        let grouped_chunks = GroupedChunks {
+            stream_header_chunks: vec![StreamHeaderChunk {
+                stream_id: 3,
+                info: StreamHeaderChunkInfo {
+                    channel_count: 1,
+                    nominal_srate: None,
+                    channel_format: Format::String,
+                    name: None,
+                    stream_type: None,
+                },
+                xml,
+            }],
+            stream_footer_chunks: vec![],
+            clock_offsets: HashMap::from([(
+                3,
+                vec![
+                    ClockOffsetChunk {
+                        stream_id: 3,
+                        collection_time: 0.0,
+                        offset_value: -1.0,
+                    },
+                    ClockOffsetChunk {
+                        stream_id: 3,
+                        collection_time: 1.0,
+                        offset_value: 1.0,
+                    },
+                ],
+            )]),
+            sample_map: HashMap::from([(3, vec![samples.into_iter()])]),
+            unknown_chunks: vec![],
+        };
+
+        let streams = process_streams(grouped_chunks, &ParseOptions::default()).unwrap();
+
+        let timestamps: Vec<_> = streams[0].samples.iter().map(|s| s.timestamp.unwrap()).collect();
+        assert_eq!(timestamps, vec![-1.0, 0.5, 3.0]);
+        assert!(matches!(streams[0].clock_model, ClockModel::PiecewiseLinear(_)));
+    }
+
+    // a chunk with a four byte length, covering the tag and the content
+    fn chunk(tag: u16, content: &[u8]) -> Vec<u8> {
+        let length = u32::try_from(content.len() + 2).unwrap();
+        let mut chunk = vec![4];
+        chunk.extend_from_slice(&length.to_le_bytes());
+        chunk.extend_from_slice(&tag.to_le_bytes());
+        chunk.extend_from_slice(content);
+        chunk
+    }
+
+    // a file with a header chunk for each stream, in the given order
+    fn file_with_streams(streams: &[(u32, &str)]) -> Vec<u8> {
+        let mut bytes = b"XDF:".to_vec();
+        bytes.extend(chunk(1, b"<?xml version=\"1.0\"?><info><version>1.0</version></info>"));
+        for (stream_id, name) in streams {
+            let mut content = stream_id.to_le_bytes().to_vec();
+            content.extend_from_slice(
+                format!(
+                    "<?xml version=\"1.0\"?><info><name>{name}</name><channel_count>1</channel_count>\
+                     <nominal_srate>10</nominal_srate><channel_format>float32</channel_format></info>"
+                )
+                .as_bytes(),
+            );
+            bytes.extend(chunk(2, &content));
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_stream_order() {
+        // the file order differs from both the id and the name order
+        let bytes = file_with_streams(&[(2, "EEG"), (3, "Audio"), (1, "Markers")]);
+        let ids = |stream_order| {
+            let options = ParseOptions {
+                stream_order,
+                ..Default::default()
+            };
+            let xdf_file = XDFFile::from_bytes_with_options(&bytes, &options).unwrap();
+            xdf_file.streams.iter().map(|s| s.id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(StreamOrder::File), vec![2, 3, 1]);
+        assert_eq!(ids(StreamOrder::Id), vec![1, 2, 3]);
+        assert_eq!(ids(StreamOrder::Name), vec![3, 2, 1]);
+    }
+
     #[test]
     const fn test_is_sync() {
         const fn is_sync<T: Sync>() {}
@@ -656,4 +1016,11 @@ mod tests {
         const fn is_send<T: Send>() {}
         is_send::<XDFFile>();
     }
+
+    #[test]
+    const fn test_is_unwind_safe() {
+        const fn is_unwind_safe<T: std::panic::UnwindSafe + std::panic::RefUnwindSafe>() {}
+        is_unwind_safe::<XDFFile>();
+        is_unwind_safe::<Stream>();
+    }
 }
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::{ClockSync, PiecewiseLinearSync, TimeOrigin};
+
+/// The order of [`crate::XDFFile::streams`] after parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StreamOrder {
+    /// The order of the stream headers in the file.
+    #[default]
+    File,
+    /// Ascending stream ids.
+    Id,
+    /// Alphabetical by name, with unnamed streams last. Streams with the same name are ordered by id.
+    Name,
+}
+
+/// Options for parsing an XDF file with [`crate::XDFFile::from_bytes_with_options`].
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// The strategy used to correct the timestamps of each stream using its clock offsets.
+    /// Defaults to [`PiecewiseLinearSync`].
+    pub clock_sync: Arc<dyn ClockSync>,
+    /// The origin of the timestamps of all streams. Defaults to [`TimeOrigin::Recorder`], i.e. the timestamps are left as they are.
+    pub time_origin: TimeOrigin,
+    /// The order of the parsed streams. Defaults to [`StreamOrder::File`].
+    pub stream_order: StreamOrder,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            clock_sync: Arc::new(PiecewiseLinearSync),
+            time_origin: TimeOrigin::default(),
+            stream_order: StreamOrder::default(),
+        }
+    }
+}
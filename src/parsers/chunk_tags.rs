@@ -1,4 +1,10 @@
-use nom::{self, bytes::complete::tag, combinator::value, IResult, Parser};
+use nom::{
+    self,
+    bytes::complete::tag,
+    combinator::{value, verify},
+    number::complete::le_u16,
+    IResult, Parser,
+};
 
 use crate::chunk_structs::Tag;
 
@@ -39,3 +45,8 @@ pub(crate) fn boundary_tag(input: &[u8]) -> IResult<&[u8], Tag> {
 pub(crate) fn stream_footer_tag(input: &[u8]) -> IResult<&[u8], Tag> {
     value(Tag::StreamFooter, tag([6, 0])).parse(input)
 }
+
+// parser for any tag which is not one of the above
+pub(crate) fn unknown_tag(input: &[u8]) -> IResult<&[u8], u16> {
+    verify(le_u16, |tag| !(1..=6).contains(tag)).parse(input)
+}
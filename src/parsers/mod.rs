@@ -9,6 +9,7 @@ mod samples;
 mod stream_footer;
 mod stream_header;
 mod stream_id;
+mod unknown_chunk;
 mod values;
 mod xml;
 
@@ -22,5 +23,6 @@ use samples::samples;
 use stream_footer::stream_footer;
 use stream_header::stream_header;
 use stream_id::stream_id;
+use unknown_chunk::unknown_chunk;
 use values::values;
 use xml::xml;
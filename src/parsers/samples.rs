@@ -3,14 +3,13 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 use nom::{
     combinator,
     error::context,
-    multi,
     number::complete::{le_f64, u8},
     IResult,
 };
 
 use crate::{
-    chunk_structs::{SamplesChunk, StreamHeaderChunkInfo},
-    Format, Sample,
+    chunk_structs::{ChunkValues, SamplesChunk, StreamHeaderChunkInfo},
+    Format,
 };
 
 use super::{chunk_content, chunk_length::length, chunk_tags::samples_tag, stream_id, values};
@@ -35,11 +34,14 @@ fn optional_timestamp(input: &[u8]) -> IResult<&[u8], Option<f64>> {
 // [0 or 8] [Double, in seconds] [Value as defined by format] ...
 // [1][8 if TimeStampBytes==8, 0 if TimeStampBytes==0] [[Variable]] ...
 
-fn sample(input: &[u8], num_channels: usize, format: Format) -> IResult<&[u8], Sample> {
-    let (input, timestamp) = context("sample optional_timestamp", optional_timestamp)(input)?;
-    let (input, values) = context("sample values", |i| values(i, format, num_channels))(input)?;
-
-    Ok((input, Sample { timestamp, values }))
+// the size of a single value in bytes, at least one byte for strings
+const fn value_size(format: Format) -> usize {
+    match format {
+        Format::Int8 | Format::String => 1,
+        Format::Int16 => 2,
+        Format::Int32 | Format::Float32 => 4,
+        Format::Int64 | Format::Float64 => 8,
+    }
 }
 
 #[allow(clippy::needless_pass_by_value)]
@@ -52,7 +54,7 @@ pub(super) fn samples(
     let (input, chunk_content) = context("samples chunk_content", chunk_content)(input)?;
     let (chunk_content, _tag) = context("samples tag", samples_tag)(chunk_content)?; // 2 bytes
     let (chunk_content, stream_id) = context("samples stream_id", stream_id)(chunk_content)?; // 4 bytes
-    let (chunk_content, num_samples) = context("samples num_samples", length)(chunk_content)?;
+    let (mut chunk_content, num_samples) = context("samples num_samples", length)(chunk_content)?;
 
     let Some(stream_info) = stream_info.get(&stream_id) else {
         return context("samples get(&stream_id), missing a stream header", combinator::fail)(&[0]);
@@ -60,7 +62,60 @@ pub(super) fn samples(
     let num_channels = stream_info.channel_count as usize;
     let format = stream_info.channel_format;
 
-    let (_chunk_content, samples) = multi::count(|i| sample(i, num_channels, format), num_samples)(chunk_content)?;
+    // the counts come from the file, so the buffers are never allocated larger than the chunk could hold
+    let timestamps_capacity = num_samples.min(chunk_content.len());
+    let values_capacity = num_samples
+        .saturating_mul(num_channels)
+        .min(chunk_content.len() / value_size(format));
+
+    let mut timestamps = Vec::with_capacity(timestamps_capacity);
+    let mut buffer = ChunkValues::with_capacity(format, values_capacity);
+
+    for _ in 0..num_samples {
+        let (rest, timestamp) = context("sample optional_timestamp", optional_timestamp)(chunk_content)?;
+        let (rest, ()) = context("sample values", |i| values(i, &mut buffer, num_channels))(rest)?;
+        timestamps.push(timestamp);
+        chunk_content = rest;
+    }
+
+    Ok((
+        input,
+        SamplesChunk {
+            stream_id,
+            num_channels,
+            timestamps,
+            values: buffer,
+        },
+    ))
+}
+
+#[test]
+fn test_samples_chunk() {
+    let stream_info = HashMap::from([(
+        7,
+        StreamHeaderChunkInfo {
+            channel_count: 2,
+            nominal_srate: Some(10.0),
+            channel_format: Format::Int16,
+            name: None,
+            stream_type: None,
+        },
+    )]);
+
+    // tag, stream id and two samples of two int16 values, only the first with a timestamp
+    let mut content = vec![3, 0, 7, 0, 0, 0, 1, 2, 8];
+    content.extend_from_slice(&1.5_f64.to_le_bytes());
+    content.extend_from_slice(&[0x01, 0x00, 0xFF, 0xFF, 0, 0x02, 0x00, 0xFE, 0xFF]);
+    let mut input = vec![1, u8::try_from(content.len()).unwrap()];
+    input.extend(content);
+
+    let (remainder, chunk) = samples(&input, Rc::new(RefCell::new(stream_info))).unwrap();
+    assert!(remainder.is_empty());
+    assert_eq!(chunk.values, ChunkValues::Int16(vec![1, -1, 2, -2]));
 
-    Ok((input, SamplesChunk { stream_id, samples }))
+    let samples: Vec<_> = chunk.into_iter().collect();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].timestamp, Some(1.5));
+    assert_eq!(samples[1].timestamp, None);
+    assert_eq!(samples[1].values, crate::Values::Int16(vec![2, -2]));
 }
@@ -0,0 +1,27 @@
+use nom::{error::context, IResult};
+
+use crate::chunk_structs::UnknownChunk;
+
+use super::{chunk_content, chunk_tags::unknown_tag};
+
+// unknown chunk structure
+// [Tag] [Content]
+// [Not 1 to 6] [Arbitrary]
+// [2] [As determined by chunk length]
+
+// `file_length` is the length of the whole file, which is needed to find the chunk's offset
+pub(crate) fn unknown_chunk(input: &[u8], file_length: usize) -> IResult<&[u8], UnknownChunk> {
+    let offset = file_length - input.len();
+    let (input, chunk_content) = context("unknown_chunk chunk_content", chunk_content)(input)?;
+
+    let (chunk_content, tag) = context("unknown_chunk tag", unknown_tag)(chunk_content)?; // 2 bytes
+
+    Ok((
+        input,
+        UnknownChunk {
+            tag,
+            offset,
+            bytes: chunk_content.to_vec(),
+        },
+    ))
+}
@@ -1,6 +1,6 @@
-use nom::{combinator, error::context, multi, number, IResult};
+use nom::{combinator, error::context, IResult};
 
-use crate::{Format, Values};
+use crate::chunk_structs::ChunkValues;
 
 use super::chunk_length::length;
 
@@ -24,45 +24,81 @@ fn string_value(input: &[u8]) -> IResult<&[u8], String> {
 // [Arbitrary]
 // [8, 4, 2 or 1]
 
-pub(super) fn values(input: &[u8], format: Format, num_values: usize) -> IResult<&[u8], Values> {
-    let mut input = input;
-    let values = match format {
-        Format::Float32 => {
-            let (inp, values) = context("values Float32", multi::count(number::complete::le_f32, num_values))(input)?;
-            input = inp;
-            Values::Float32(values)
-        }
-        Format::Float64 => {
-            let (inp, values) = context("values Float64", multi::count(number::complete::le_f64, num_values))(input)?;
-            input = inp;
-            Values::Float64(values)
-        }
-        Format::Int8 => {
-            let (inp, values) = context("values Int8", multi::count(number::complete::le_i8, num_values))(input)?;
-            input = inp;
-            Values::Int8(values)
-        }
-        Format::Int16 => {
-            let (inp, values) = context("values Int16", multi::count(number::complete::le_i16, num_values))(input)?;
-            input = inp;
-            Values::Int16(values)
-        }
-        Format::Int32 => {
-            let (inp, values) = context("values Int32", multi::count(number::complete::le_i32, num_values))(input)?;
-            input = inp;
-            Values::Int32(values)
-        }
-        Format::Int64 => {
-            let (inp, values) = context("values Int64", multi::count(number::complete::le_i64, num_values))(input)?;
-            input = inp;
-            Values::Int64(values)
-        }
-        Format::String => {
-            let (inp, string) = context("values String", string_value)(input)?;
-            input = inp;
-            Values::String(string)
-        }
+// numbers which can be decoded from their little-endian bytes
+trait LeBytes: Sized {
+    const SIZE: usize;
+
+    // `bytes` must be exactly SIZE bytes long
+    fn from_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_le_bytes {
+    ($($type:ty),*) => {
+        $(
+            impl LeBytes for $type {
+                const SIZE: usize = std::mem::size_of::<$type>();
+
+                fn from_le(bytes: &[u8]) -> Self {
+                    let mut array = [0; std::mem::size_of::<$type>()];
+                    array.copy_from_slice(bytes);
+                    Self::from_le_bytes(array)
+                }
+            }
+        )*
     };
+}
+
+impl_le_bytes!(i8, i16, i32, i64, f32, f64);
+
+// decodes all values of a sample at once, since numeric values are stored back to back
+fn le_values<'a, T: LeBytes>(input: &'a [u8], buffer: &mut Vec<T>, num_values: usize) -> IResult<&'a [u8], ()> {
+    let (input, bytes) = nom::bytes::complete::take(num_values.saturating_mul(T::SIZE))(input)?;
+    buffer.extend(bytes.chunks_exact(T::SIZE).map(T::from_le));
+
+    Ok((input, ()))
+}
+
+// appends the values of a single sample to the chunk's buffer
+pub(super) fn values<'a>(input: &'a [u8], buffer: &mut ChunkValues, num_values: usize) -> IResult<&'a [u8], ()> {
+    match buffer {
+        ChunkValues::Int8(v) => context("values Int8", |i| le_values(i, v, num_values))(input),
+        ChunkValues::Int16(v) => context("values Int16", |i| le_values(i, v, num_values))(input),
+        ChunkValues::Int32(v) => context("values Int32", |i| le_values(i, v, num_values))(input),
+        ChunkValues::Int64(v) => context("values Int64", |i| le_values(i, v, num_values))(input),
+        ChunkValues::Float32(v) => context("values Float32", |i| le_values(i, v, num_values))(input),
+        ChunkValues::Float64(v) => context("values Float64", |i| le_values(i, v, num_values))(input),
+        ChunkValues::String(v) => {
+            let mut input = input;
+            for _ in 0..num_values {
+                let (rest, string) = context("values String", string_value)(input)?;
+                v.push(string);
+                input = rest;
+            }
+            Ok((input, ()))
+        }
+    }
+}
+
+#[test]
+fn test_multi_channel_string_values() {
+    // two strings followed by the timestamp byte of the next sample
+    let input = [1, 3, b'a', b'b', b'c', 1, 2, b'd', b'e', 0];
+
+    let mut buffer = ChunkValues::String(Vec::new());
+    let (remainder, ()) = values(&input, &mut buffer, 2).unwrap();
+    assert_eq!(remainder, &[0]);
+    assert_eq!(buffer, ChunkValues::String(vec!["abc".to_string(), "de".to_string()]));
+}
+
+#[test]
+fn test_le_values() {
+    // two little-endian int16 values appended to an existing one
+    let input = [0x01, 0x00, 0xFF, 0xFF, 0x2A];
+
+    let mut buffer = ChunkValues::Int16(vec![7]);
+    let (remainder, ()) = values(&input, &mut buffer, 2).unwrap();
+    assert_eq!(remainder, &[0x2A]);
+    assert_eq!(buffer, ChunkValues::Int16(vec![7, 1, -1]));
 
-    Ok((input, values))
+    assert!(values(&input, &mut ChunkValues::Float64(Vec::new()), 1).is_err());
 }
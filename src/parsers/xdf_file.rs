@@ -4,7 +4,7 @@ use nom::{branch::alt, bytes::complete::tag, combinator::map, error::context, mu
 
 use crate::chunk_structs::{Chunk, StreamHeaderChunkInfo};
 
-use super::{boundary, clock_offset, file_header, samples, stream_footer, stream_header};
+use super::{boundary, clock_offset, file_header, samples, stream_footer, stream_header, unknown_chunk};
 
 // structure of an XDF file:
 // [MagicCode] [Chunk] [Chunk] [Chunk] ...
@@ -47,12 +47,17 @@ pub(crate) fn xdf_file_parser(input: &[u8]) -> IResult<&[u8], Vec<Chunk>> {
     let stream_footer_parser = map(stream_footer, Chunk::StreamFooter);
     let stream_footer_parser = context("xdf_file stream_footer", stream_footer_parser);
 
+    // chunks with other tags are skipped using their length so that the rest of the file can still be read
+    let unknown_chunk_parser = map(|i| unknown_chunk(i, input.len()), Chunk::Unknown);
+    let unknown_chunk_parser = context("xdf_file unknown_chunk", unknown_chunk_parser);
+
     let repeated_parsers = many0(alt((
         stream_header_parser,
         samples_parser,
         clock_offset_parser,
         boundary_parser,
         stream_footer_parser,
+        unknown_chunk_parser,
     )));
     let mut repeated_parsers = context("xdf_file repeated_parsers", repeated_parsers);
 
@@ -95,4 +100,27 @@ mod tests {
         assert!(matches!(chunks[13], Chunk::StreamFooter(_)));
         assert!(matches!(chunks[14], Chunk::StreamFooter(_)));
     }
+
+    #[test]
+    fn test_unknown_chunk() {
+        // insert a chunk with tag 7 and three bytes of content right after the file header chunk
+        let mut input = include_bytes!("../../tests/minimal.xdf").to_vec();
+        input.splice(64..64, [1, 5, 7, 0, 1, 2, 3]);
+
+        let (rest, chunks) = xdf_file_parser(&input).unwrap();
+
+        assert_eq!(rest, &[] as &[u8]);
+        assert_eq!(chunks.len(), 16);
+        assert!(matches!(chunks[0], Chunk::FileHeader(_)));
+        match &chunks[1] {
+            Chunk::Unknown(chunk) => {
+                assert_eq!(chunk.tag, 7);
+                assert_eq!(chunk.offset, 64);
+                assert_eq!(chunk.bytes, vec![1, 2, 3]);
+            }
+            other => panic!("expected an unknown chunk, got {other:?}"),
+        }
+        assert!(matches!(chunks[2], Chunk::StreamHeader(_)));
+        assert!(matches!(chunks[15], Chunk::StreamFooter(_)));
+    }
 }
@@ -0,0 +1,134 @@
+use crate::{errors::StreamError, Stream, XDFFile};
+
+// Lookups of single streams fail if no stream or more than one stream matches,
+// so that analysis scripts do not silently pick the wrong one.
+impl XDFFile {
+    fn unique_stream(&self, query: String, predicate: impl Fn(&Stream) -> bool) -> Result<&Stream, StreamError> {
+        let mut matches = self.streams.iter().filter(|s| predicate(s));
+
+        match (matches.next(), matches.next()) {
+            (Some(stream), None) => Ok(stream),
+            (None, _) => Err(StreamError::StreamNotFound(query)),
+            (Some(first), Some(second)) => Err(StreamError::AmbiguousStream {
+                query,
+                ids: [first.id, second.id].into_iter().chain(matches.map(|s| s.id)).collect(),
+            }),
+        }
+    }
+
+    /**
+    Returns the stream with the given id.
+
+    # Errors
+    [`StreamError::StreamNotFound`] if there is no such stream.
+    */
+    pub fn stream_by_id(&self, id: u32) -> Result<&Stream, StreamError> {
+        self.unique_stream(format!("id {id}"), |s| s.id == id)
+    }
+
+    /**
+    Returns the only stream with the given name.
+
+    # Errors
+    * [`StreamError::StreamNotFound`] if there is no such stream.
+    * [`StreamError::AmbiguousStream`] if there are several.
+    */
+    pub fn stream_by_name(&self, name: &str) -> Result<&Stream, StreamError> {
+        self.unique_stream(format!("name {name:?}"), |s| s.name.as_deref() == Some(name))
+    }
+
+    /**
+    Returns the only stream with the given type, for example `EEG`.
+    Use [`XDFFile::streams_where`] if there may be several.
+
+    # Errors
+    * [`StreamError::StreamNotFound`] if there is no such stream.
+    * [`StreamError::AmbiguousStream`] if there are several.
+    */
+    pub fn stream_by_type(&self, stream_type: &str) -> Result<&Stream, StreamError> {
+        self.unique_stream(format!("type {stream_type:?}"), |s| {
+            s.r#type.as_deref() == Some(stream_type)
+        })
+    }
+
+    /**
+    Returns the only stream with the given uid, see [`crate::StreamInfo::uid`].
+
+    # Errors
+    * [`StreamError::StreamNotFound`] if there is no such stream.
+    * [`StreamError::AmbiguousStream`] if there are several.
+    */
+    pub fn stream_by_uid(&self, uid: &str) -> Result<&Stream, StreamError> {
+        self.unique_stream(format!("uid {uid:?}"), |s| s.info.uid.as_deref() == Some(uid))
+    }
+
+    /**
+    Returns the only stream with the given source id, see [`crate::StreamInfo::source_id`].
+
+    # Errors
+    * [`StreamError::StreamNotFound`] if there is no such stream.
+    * [`StreamError::AmbiguousStream`] if there are several, for example because the source was recorded twice.
+    */
+    pub fn stream_by_source_id(&self, source_id: &str) -> Result<&Stream, StreamError> {
+        self.unique_stream(format!("source_id {source_id:?}"), |s| {
+            s.info.source_id.as_deref() == Some(source_id)
+        })
+    }
+
+    /// Returns all streams for which `predicate` returns true, in the order of [`XDFFile::streams`].
+    pub fn streams_where(&self, mut predicate: impl FnMut(&Stream) -> bool) -> Vec<&Stream> {
+        self.streams.iter().filter(|s| predicate(s)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Format;
+
+    fn stream(id: u32, name: &str, stream_type: &str) -> Stream {
+        let mut stream = Stream::from_test_samples(Format::Float32, 1, None, Vec::new());
+        stream.id = id;
+        stream.name = Some(name.into());
+        stream.r#type = Some(stream_type.into());
+        stream.info.uid = Some(format!("uid-{id}"));
+        stream
+    }
+
+    fn xdf_file() -> XDFFile {
+        XDFFile::from_test_streams(vec![
+            stream(1, "EEG", "EEG"),
+            stream(2, "Markers", "Markers"),
+            stream(3, "EEG", "EEG"),
+        ])
+    }
+
+    #[test]
+    fn test_unique_lookups() {
+        let xdf_file = xdf_file();
+
+        assert_eq!(xdf_file.stream_by_id(2).unwrap().id, 2);
+        assert_eq!(xdf_file.stream_by_name("Markers").unwrap().id, 2);
+        assert_eq!(xdf_file.stream_by_type("Markers").unwrap().id, 2);
+        assert_eq!(xdf_file.stream_by_uid("uid-3").unwrap().id, 3);
+
+        assert!(matches!(xdf_file.stream_by_id(4), Err(StreamError::StreamNotFound(_))));
+        assert!(matches!(
+            xdf_file.stream_by_source_id("amp"),
+            Err(StreamError::StreamNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_ambiguous_lookups() {
+        let xdf_file = xdf_file();
+
+        match xdf_file.stream_by_name("EEG") {
+            Err(StreamError::AmbiguousStream { ids, .. }) => assert_eq!(ids, vec![1, 3]),
+            other => panic!("expected an ambiguous match, got {other:?}"),
+        }
+
+        let eeg_streams = xdf_file.streams_where(|s| s.r#type.as_deref() == Some("EEG"));
+        assert_eq!(eeg_streams.iter().map(|s| s.id).collect::<Vec<_>>(), vec![1, 3]);
+    }
+}
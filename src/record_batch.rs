@@ -0,0 +1,235 @@
+//! Conversion of streams into Arrow [`RecordBatch`]es, available with the `arrow` feature.
+
+use std::{collections::HashMap, sync::Arc};
+
+use arrow_array::{
+    types::{Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type},
+    ArrayRef, ArrowPrimitiveType, Float64Array, PrimitiveArray, RecordBatch, StringArray,
+};
+use arrow_schema::{DataType, Field, Schema};
+
+use crate::{errors::StreamError, Format, Stream, Values, XdfScalar};
+
+impl Stream {
+    /**
+    Converts the stream into an Arrow [`RecordBatch`], for example to analyse it with Polars.
+
+    The first column is `timestamp`, which is null for samples without a timestamp.
+    It is followed by one column per channel whose type follows the stream's [`Format`].
+    The columns are named after the labels in [`Stream::channels`] if present, otherwise `channel_<index>`.
+    Since the names must be unique, labels which occur more than once, are `timestamp`
+    or are the fallback name of another channel are replaced by `channel_<index>` as well.
+
+    The stream's id, name, type, format, nominal sampling rate and uid are stored in the schema metadata,
+    under the keys `stream_id`, `name`, `type`, `channel_format`, `nominal_srate` and `uid`. Missing values are left out.
+
+    # Errors
+    [`StreamError::MismatchedValues`] if the values of a sample do not match the stream's format or channel count.
+    */
+    pub fn to_record_batch(&self) -> Result<RecordBatch, StreamError> {
+        let channel_count = self.channel_count as usize;
+
+        let channels = match self.format {
+            Format::Int8 => self.primitive_columns::<Int8Type>(channel_count)?,
+            Format::Int16 => self.primitive_columns::<Int16Type>(channel_count)?,
+            Format::Int32 => self.primitive_columns::<Int32Type>(channel_count)?,
+            Format::Int64 => self.primitive_columns::<Int64Type>(channel_count)?,
+            Format::Float32 => self.primitive_columns::<Float32Type>(channel_count)?,
+            Format::Float64 => self.primitive_columns::<Float64Type>(channel_count)?,
+            Format::String => self.string_columns(channel_count)?,
+        };
+
+        let timestamps: ArrayRef = Arc::new(self.samples.iter().map(|s| s.timestamp).collect::<Float64Array>());
+
+        let fields: Vec<Field> = std::iter::once(Field::new("timestamp", DataType::Float64, true))
+            .chain(
+                self.field_names(channel_count)
+                    .into_iter()
+                    .zip(&channels)
+                    .map(|(name, column)| Field::new(name, column.data_type().clone(), false)),
+            )
+            .collect();
+
+        let schema = Schema::new_with_metadata(fields, self.schema_metadata());
+        let columns = std::iter::once(timestamps).chain(channels).collect();
+
+        RecordBatch::try_new(Arc::new(schema), columns).map_err(|_| StreamError::MismatchedValues(self.format))
+    }
+
+    fn primitive_columns<A: ArrowPrimitiveType>(&self, channel_count: usize) -> Result<Vec<ArrayRef>, StreamError>
+    where
+        A::Native: XdfScalar,
+    {
+        let rows = self.data::<A::Native>()?;
+        if rows.iter().any(|row| row.len() != channel_count) {
+            return Err(StreamError::MismatchedValues(self.format));
+        }
+
+        Ok((0..channel_count)
+            .map(|c| Arc::new(PrimitiveArray::<A>::from_iter_values(rows.iter().map(|row| row[c]))) as ArrayRef)
+            .collect())
+    }
+
+    fn string_columns(&self, channel_count: usize) -> Result<Vec<ArrayRef>, StreamError> {
+        let rows = self
+            .samples
+            .iter()
+            .map(|s| match &s.values {
+                Values::String(values) if values.len() == channel_count => Ok(values),
+                _ => Err(StreamError::MismatchedValues(self.format)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((0..channel_count)
+            .map(|c| Arc::new(rows.iter().map(|row| Some(row[c].as_str())).collect::<StringArray>()) as ArrayRef)
+            .collect())
+    }
+
+    // the channel labels, or channel_<index> where a label is missing or would not be a unique column name
+    fn field_names(&self, channel_count: usize) -> Vec<String> {
+        let labels: Vec<Option<&str>> = (0..channel_count)
+            .map(|i| self.channels.get(i).and_then(|channel| channel.label.as_deref()))
+            .collect();
+        let fallback = |i: usize| format!("channel_{i}");
+
+        labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| match label {
+                Some(label)
+                    if *label != "timestamp"
+                        && labels.iter().filter(|&&other| other == Some(*label)).count() == 1
+                        && (0..channel_count).all(|j| j == i || *label != fallback(j)) =>
+                {
+                    (*label).to_string()
+                }
+                _ => fallback(i),
+            })
+            .collect()
+    }
+
+    fn schema_metadata(&self) -> HashMap<String, String> {
+        let mut metadata = HashMap::from([
+            ("stream_id".to_string(), self.id.to_string()),
+            ("channel_format".to_string(), self.format.as_xdf_str().to_string()),
+            (
+                "nominal_srate".to_string(),
+                self.nominal_srate.unwrap_or(0.0).to_string(),
+            ),
+        ]);
+
+        if let Some(name) = &self.name {
+            metadata.insert("name".to_string(), name.to_string());
+        }
+        if let Some(stream_type) = &self.r#type {
+            metadata.insert("type".to_string(), stream_type.to_string());
+        }
+        if let Some(uid) = &self.info.uid {
+            metadata.insert("uid".to_string(), uid.clone());
+        }
+
+        metadata
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::{Array, Int32Array};
+
+    use super::*;
+    use crate::ChannelInfo;
+
+    fn int_stream() -> Stream {
+        let samples = (0..3).map(|i| ((i != 1).then_some(f64::from(i)), Values::Int32(vec![i, -i])));
+        let mut stream = Stream::from_test_timestamps(Format::Int32, 2, Some(1.0), samples);
+        stream.name = Some("EEG stream".into());
+        stream.info.uid = Some("abc".to_string());
+        stream.channels = vec![
+            ChannelInfo {
+                label: Some("Cz".to_string()),
+                ..Default::default()
+            },
+            ChannelInfo::default(),
+        ];
+        stream
+    }
+
+    #[test]
+    fn test_record_batch() {
+        let batch = int_stream().to_record_batch().unwrap();
+        let schema = batch.schema();
+
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.num_columns(), 3);
+        assert_eq!(schema.field(0).name(), "timestamp");
+        assert_eq!(schema.field(1).name(), "Cz");
+        assert_eq!(schema.field(2).name(), "channel_1");
+        assert_eq!(schema.field(1).data_type(), &DataType::Int32);
+
+        assert!(batch.column(0).is_null(1));
+        let channel = batch.column(2).as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(channel.values().to_vec(), vec![0, -1, -2]);
+
+        let metadata = schema.metadata();
+        assert_eq!(metadata["name"], "EEG stream");
+        assert_eq!(metadata["uid"], "abc");
+        assert_eq!(metadata["nominal_srate"], "1");
+        assert_eq!(metadata["channel_format"], "int32");
+        assert!(!metadata.contains_key("type"));
+    }
+
+    #[test]
+    fn test_unique_field_names() {
+        let labels = ["timestamp", "Cz", "Fz", "Cz", "channel_0", "channel_5"];
+        let samples = [(0.0, Values::Int32(vec![0; labels.len()]))];
+        let mut stream = Stream::from_test_timestamps(Format::Int32, 6, Some(1.0), samples);
+        stream.channels = labels
+            .iter()
+            .map(|label| ChannelInfo {
+                label: Some((*label).to_string()),
+                ..Default::default()
+            })
+            .collect();
+
+        let batch = stream.to_record_batch().unwrap();
+        let names: Vec<_> = batch.schema().fields().iter().map(|f| f.name().clone()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "timestamp",
+                "channel_0",
+                "channel_1",
+                "Fz",
+                "channel_3",
+                "channel_4",
+                "channel_5"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_record_batch() {
+        let stream = Stream::from_test_timestamps(
+            Format::String,
+            1,
+            None,
+            [(1.0, Values::String(vec!["marker".to_string()]))],
+        );
+
+        let batch = stream.to_record_batch().unwrap();
+        assert_eq!(batch.schema().field(1).data_type(), &DataType::Utf8);
+        let column = batch.column(1).as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(column.value(0), "marker");
+    }
+
+    #[test]
+    fn test_mismatched_values() {
+        let mut stream = int_stream();
+        stream.samples[0].values = Values::Int32(vec![1]);
+
+        assert!(matches!(
+            stream.to_record_batch(),
+            Err(StreamError::MismatchedValues(Format::Int32))
+        ));
+    }
+}
@@ -0,0 +1,347 @@
+use crate::{errors::StreamError, util::set_text_of_child, Format, Sample, Stream, Values};
+
+/// The interpolation used by [`Stream::resample`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResampleMethod {
+    /// Linear interpolation between the two neighbouring samples. Fast, but does not filter when downsampling.
+    Linear,
+    /// Windowed sinc (Lanczos) interpolation with `half_width` input samples on either side.
+    /// When downsampling, the kernel is widened to act as an anti-aliasing low-pass filter.
+    Sinc {
+        /// The number of zero crossings of the kernel on either side, 3 to 16 are common choices.
+        half_width: usize,
+    },
+}
+
+impl Stream {
+    /**
+    Resamples a numeric stream onto the uniform time grid `start_time + k / srate`.
+
+    The position of each grid point is located using the actual (clock corrected) timestamps of the samples,
+    so irregular timestamps are handled correctly rather than assuming the samples are evenly spaced.
+    Only grid points between the first and the last timestamp of the stream are produced,
+    which means streams resampled with the same `srate` and `start_time` share their timestamps.
+    Samples without a timestamp are ignored.
+
+    # Returns
+    A new [`Stream`] with [`Format::Float64`] values and a nominal sampling rate of `srate`. Its header is updated accordingly.
+    The footer is dropped since it describes the original samples.
+
+    # Errors
+    * [`StreamError::UnsupportedFormat`] for string streams.
+    * [`StreamError::InvalidSrate`] if `srate` is not a positive, finite number.
+    * [`StreamError::MismatchedValues`] if the values of a sample are not numeric or do not match the channel count.
+    */
+    pub fn resample(&self, srate: f64, start_time: f64, method: ResampleMethod) -> Result<Self, StreamError> {
+        if self.format == Format::String {
+            return Err(StreamError::UnsupportedFormat(self.format));
+        }
+        if !(srate > 0.0 && srate.is_finite()) {
+            return Err(StreamError::InvalidSrate(srate));
+        }
+
+        let (timestamps, channels) = self.timestamped_channels()?;
+
+        let grid = match (timestamps.first(), timestamps.last()) {
+            (Some(&first), Some(&last)) => grid_between(first, last, srate, start_time),
+            _ => Vec::new(),
+        };
+
+        let resampled: Vec<Vec<f64>> = match method {
+            ResampleMethod::Linear => channels
+                .iter()
+                .map(|channel| grid.iter().map(|&t| linear_at(&timestamps, channel, t)).collect())
+                .collect(),
+            ResampleMethod::Sinc { half_width } => {
+                let input_srate = if timestamps.len() > 1 {
+                    (timestamps.len() - 1) as f64 / (timestamps[timestamps.len() - 1] - timestamps[0])
+                } else {
+                    srate
+                };
+                // when downsampling, lower the cutoff to the new Nyquist frequency.
+                // Samples which all share one timestamp have no usable rate, they are not filtered.
+                let ratio = if input_srate > 0.0 && input_srate.is_finite() {
+                    (srate / input_srate).min(1.0)
+                } else {
+                    1.0
+                };
+
+                let positions: Vec<(usize, f64)> = grid.iter().map(|&t| fractional_index(&timestamps, t)).collect();
+                channels
+                    .iter()
+                    .map(|channel| {
+                        positions
+                            .iter()
+                            .map(|&p| sinc_at(channel, p, half_width, ratio))
+                            .collect()
+                    })
+                    .collect()
+            }
+        };
+
+        let samples = grid
+            .iter()
+            .enumerate()
+            .map(|(i, &t)| Sample {
+                timestamp: Some(t),
+                values: Values::Float64(resampled.iter().map(|channel| channel[i]).collect()),
+            })
+            .collect();
+
+        let mut header = self.header.clone();
+        set_text_of_child(&mut header, "nominal_srate", &srate.to_string());
+        set_text_of_child(&mut header, "channel_format", Format::Float64.as_xdf_str());
+
+        Ok(Self {
+            nominal_srate: Some(srate),
+            format: Format::Float64,
+            header,
+            measured_srate: Some(srate),
+            footer: None,
+            footer_info: None,
+            samples,
+            recorded_timestamps: Vec::new(),
+            ..self.clone_without_samples()
+        })
+    }
+
+    // the timestamps of all samples which have one, and the corresponding values as one vector per channel,
+    // each as long as the timestamps. Only meant for numeric streams, string values are an error.
+    pub(crate) fn timestamped_channels(&self) -> Result<(Vec<f64>, Vec<Vec<f64>>), StreamError> {
+        let mut timestamps = Vec::with_capacity(self.samples.len());
+        let mut channels = vec![Vec::with_capacity(self.samples.len()); self.channel_count as usize];
+
+        for sample in &self.samples {
+            let Some(ts) = sample.timestamp else {
+                continue;
+            };
+            let values = sample
+                .values
+                .to_f64_vec()
+                .filter(|values| values.len() == channels.len())
+                .ok_or(StreamError::MismatchedValues(self.format))?;
+
+            timestamps.push(ts);
+            for (channel, value) in channels.iter_mut().zip(values) {
+                channel.push(value);
+            }
+        }
+
+        Ok((timestamps, channels))
+    }
+}
+
+// the points `start_time + k / srate` between `first` and `last` (inclusive)
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // the count is rounded and clamped to be non-negative
+fn grid_between(first: f64, last: f64, srate: f64, start_time: f64) -> Vec<f64> {
+    let first_k = ((first - start_time) * srate).ceil();
+    let last_k = ((last - start_time) * srate).floor();
+    let count = (last_k - first_k + 1.0).max(0.0) as usize;
+
+    (0..count).map(|n| start_time + (first_k + n as f64) / srate).collect()
+}
+
+// the position of `t` in `timestamps` as an index and the fraction of the way to the next index.
+// Clamped to the range of the timestamps, which must not be empty.
+pub(crate) fn fractional_index(timestamps: &[f64], t: f64) -> (usize, f64) {
+    let next = timestamps.partition_point(|&ts| ts <= t);
+    if next == 0 {
+        return (0, 0.0);
+    }
+    if next == timestamps.len() {
+        return (timestamps.len() - 1, 0.0);
+    }
+
+    let (t0, t1) = (timestamps[next - 1], timestamps[next]);
+    let dt = t1 - t0;
+    let fraction = if dt > 0.0 { (t - t0) / dt } else { 0.0 };
+
+    (next - 1, fraction)
+}
+
+pub(crate) fn linear_at(timestamps: &[f64], values: &[f64], t: f64) -> f64 {
+    let (i, fraction) = fractional_index(timestamps, t);
+
+    match values.get(i + 1) {
+        Some(&next) if fraction > 0.0 => values[i] * (1.0 - fraction) + next * fraction,
+        _ => values[i],
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let px = std::f64::consts::PI * x;
+        px.sin() / px
+    }
+}
+
+// windowed sinc interpolation at the fractional index `position`, with the cutoff scaled by `ratio`
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // the reach is rounded and positive
+fn sinc_at(values: &[f64], position: (usize, f64), half_width: usize, ratio: f64) -> f64 {
+    let (index, fraction) = position;
+    let half_width = half_width.max(1) as f64;
+    let reach = (half_width / ratio).ceil() as usize;
+
+    let first = index.saturating_sub(reach);
+    let last = index.saturating_add(reach).saturating_add(1).min(values.len() - 1);
+
+    let (sum, weight_sum) = (first..=last).fold((0.0, 0.0), |(sum, weight_sum), j| {
+        let distance = (index as f64 + fraction - j as f64) * ratio;
+        let weight = if distance.abs() < half_width {
+            sinc(distance) * sinc(distance / half_width)
+        } else {
+            0.0
+        };
+        (sum + values[j] * weight, weight_sum + weight)
+    });
+
+    // normalising by the sum of the weights keeps constant signals constant, also at the edges
+    if weight_sum.abs() > f64::EPSILON {
+        sum / weight_sum
+    } else {
+        values[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1E-9;
+
+    fn stream_from_fn(timestamps: &[f64], f: impl Fn(f64) -> f64) -> Stream {
+        let samples = timestamps.iter().map(|&ts| (ts, Values::Float64(vec![f(ts)])));
+        Stream::from_test_timestamps(Format::Float64, 1, Some(100.0), samples)
+    }
+
+    fn values_of(stream: &Stream) -> Vec<f64> {
+        stream
+            .samples
+            .iter()
+            .map(|s| s.values.to_f64_vec().unwrap()[0])
+            .collect()
+    }
+
+    #[test]
+    fn test_linear_irregular_timestamps() {
+        // a ramp with jittered timestamps is reproduced exactly by linear interpolation
+        let timestamps: Vec<f64> = (0..100)
+            .map(|i| f64::from(i) / 100.0 + if i % 2 == 0 { 0.002 } else { -0.003 })
+            .collect();
+        let stream = stream_from_fn(&timestamps, |t| 4.0 * t);
+
+        let resampled = stream.resample(50.0, 0.0, ResampleMethod::Linear).unwrap();
+
+        assert_eq!(resampled.format, Format::Float64);
+        assert_eq!(resampled.nominal_srate, Some(50.0));
+        assert_eq!(
+            crate::util::get_text_from_child(&resampled.header, "nominal_srate").unwrap(),
+            "50"
+        );
+
+        // the grid starts at the first point after the first sample
+        let first_timestamp = resampled.samples[0].timestamp.unwrap();
+        assert!((first_timestamp - 0.02).abs() < EPSILON);
+
+        for sample in &resampled.samples {
+            let t = sample.timestamp.unwrap();
+            let value = sample.values.to_f64_vec().unwrap()[0];
+            assert!((value - 4.0 * t).abs() < EPSILON, "{value} at {t}");
+        }
+    }
+
+    #[test]
+    fn test_sinc_downsampling() {
+        // a slow sine is preserved when downsampling from 100 Hz to 25 Hz
+        let timestamps: Vec<f64> = (0..400).map(|i| f64::from(i) / 100.0).collect();
+        let signal = |t: f64| (2.0 * std::f64::consts::PI * t).sin();
+        let stream = stream_from_fn(&timestamps, signal);
+
+        let resampled = stream
+            .resample(25.0, 0.0, ResampleMethod::Sinc { half_width: 8 })
+            .unwrap();
+        assert_eq!(resampled.samples.len(), 100);
+
+        // ignore the edges, where the kernel is truncated
+        let values = values_of(&resampled);
+        for (sample, value) in resampled.samples.iter().zip(values).skip(10).take(80) {
+            let expected = signal(sample.timestamp.unwrap());
+            assert!((value - expected).abs() < 1E-2, "{value} vs {expected}");
+        }
+    }
+
+    #[test]
+    fn test_sinc_upsampling_constant() {
+        let timestamps: Vec<f64> = (0..10).map(|i| f64::from(i) / 100.0).collect();
+        let stream = stream_from_fn(&timestamps, |_| 3.0);
+
+        let resampled = stream
+            .resample(1000.0, 0.0, ResampleMethod::Sinc { half_width: 4 })
+            .unwrap();
+        assert_eq!(resampled.samples.len(), 91);
+        assert!(values_of(&resampled).iter().all(|v| (v - 3.0).abs() < EPSILON));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_sinc_identical_timestamps() {
+        let stream = stream_from_fn(&[1.0, 1.0], |_| 2.0);
+
+        let resampled = stream
+            .resample(10.0, 0.0, ResampleMethod::Sinc { half_width: 4 })
+            .unwrap();
+        assert_eq!(resampled.samples.len(), 1);
+        assert_eq!(values_of(&resampled), vec![2.0]);
+    }
+
+    #[test]
+    fn test_resample_errors() {
+        let stream = stream_from_fn(&[0.0, 0.01], |t| t);
+        assert!(matches!(
+            stream.resample(0.0, 0.0, ResampleMethod::Linear),
+            Err(StreamError::InvalidSrate(_))
+        ));
+
+        let marker_stream = Stream::from_test_timestamps(
+            Format::String,
+            1,
+            None,
+            [(0.0, Values::String(vec!["marker".to_string()]))],
+        );
+        assert!(matches!(
+            marker_stream.resample(10.0, 0.0, ResampleMethod::Linear),
+            Err(StreamError::UnsupportedFormat(Format::String))
+        ));
+    }
+
+    #[test]
+    fn test_resample_mismatched_values() {
+        let mut stream = stream_from_fn(&[0.0, 0.01, 0.02], |t| t);
+        stream.samples[1].values = Values::Float64(vec![]);
+
+        for method in [ResampleMethod::Linear, ResampleMethod::Sinc { half_width: 2 }] {
+            assert!(matches!(
+                stream.resample(50.0, 0.0, method),
+                Err(StreamError::MismatchedValues(Format::Float64))
+            ));
+        }
+
+        // samples without a timestamp are not looked at
+        stream.samples[1].timestamp = None;
+        assert!(stream.resample(50.0, 0.0, ResampleMethod::Linear).is_ok());
+    }
+
+    #[test]
+    fn test_resample_drops_footer() {
+        let mut stream = stream_from_fn(&[0.0, 0.01], |t| t);
+        stream.footer = Some(xmltree::Element::new("info"));
+        stream.footer_info = Some(crate::StreamFooter::default());
+
+        let resampled = stream.resample(50.0, 0.0, ResampleMethod::Linear).unwrap();
+        assert!(resampled.footer.is_none());
+        assert!(resampled.footer_info.is_none());
+    }
+}
@@ -2,6 +2,7 @@ use super::Values;
 
 /// A single sample in a stream. Samples may have a timestamp and one or more values.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sample {
     /**
     The timestamp of the sample.
@@ -12,6 +13,7 @@ pub struct Sample {
     * most of the timestamps are not actually in the recording but rather calulated using the provided nominal sampling rate.
 
     Internally, streams are recorded in "chunks". The first sample in a chunk generally includes a timestamp while the rest are calculated.
+    Which timestamps were actually recorded is stored in [`crate::Stream::recorded_timestamps`].
     */
     pub timestamp: Option<f64>,
 
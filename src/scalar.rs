@@ -0,0 +1,205 @@
+use crate::{errors::StreamError, Columns, Format, Stream, Values};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/**
+The numeric types a stream's values can have: `i8`, `i16`, `i32`, `i64`, `f32` and `f64`.
+Each corresponds to exactly one [`Format`], which allows typed access without matching on [`Values`].
+
+This trait is sealed and cannot be implemented outside of this crate.
+
+# Example
+```rust
+# use std::fs;
+# use xdf::XDFFile;
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+let bytes = fs::read("tests/minimal.xdf")?;
+let xdf_file = XDFFile::from_bytes(&bytes)?;
+let stream = xdf_file.streams.iter().find(|s| s.id == 0).unwrap();
+
+let data: Vec<&[i16]> = stream.data::<i16>()?;
+assert_eq!(data[1], &[12, 22, 32]);
+# Ok(())
+# }
+```
+*/
+pub trait XdfScalar: sealed::Sealed + Copy + std::fmt::Debug + PartialEq + Send + Sync + 'static {
+    /// The format of streams with values of this type.
+    const FORMAT: Format;
+
+    /// Returns the values as a slice if they are of this type.
+    fn slice_of(values: &Values) -> Option<&[Self]>;
+
+    /// Returns the channels if the columns are of this type.
+    fn channels_of(columns: &Columns) -> Option<&[Vec<Self>]>;
+
+    /// Wraps a vector of this type in the corresponding [`Values`] variant.
+    fn into_values(values: Vec<Self>) -> Values;
+}
+
+macro_rules! impl_xdf_scalar {
+    ($type:ty, $variant:ident) => {
+        impl sealed::Sealed for $type {}
+
+        impl XdfScalar for $type {
+            const FORMAT: Format = Format::$variant;
+
+            fn slice_of(values: &Values) -> Option<&[Self]> {
+                match values {
+                    Values::$variant(v) => Some(v),
+                    _ => None,
+                }
+            }
+
+            fn channels_of(columns: &Columns) -> Option<&[Vec<Self>]> {
+                match columns {
+                    Columns::$variant(c) => Some(c),
+                    _ => None,
+                }
+            }
+
+            fn into_values(values: Vec<Self>) -> Values {
+                Values::$variant(values)
+            }
+        }
+    };
+}
+
+impl_xdf_scalar!(i8, Int8);
+impl_xdf_scalar!(i16, Int16);
+impl_xdf_scalar!(i32, Int32);
+impl_xdf_scalar!(i64, Int64);
+impl_xdf_scalar!(f32, Float32);
+impl_xdf_scalar!(f64, Float64);
+
+impl Values {
+    /// Returns the values as a slice of `T`, or None if they are of a different type.
+    #[must_use]
+    pub fn as_slice<T: XdfScalar>(&self) -> Option<&[T]> {
+        T::slice_of(self)
+    }
+}
+
+impl Columns {
+    /// Returns the channels as vectors of `T`, or None if they are of a different type.
+    #[must_use]
+    pub fn channels<T: XdfScalar>(&self) -> Option<&[Vec<T>]> {
+        T::channels_of(self)
+    }
+}
+
+impl Stream {
+    /**
+    Returns the values of all samples as a matrix of `T`, with one row per sample.
+
+    # Errors
+    * [`StreamError::WrongFormat`] if the stream's format does not correspond to `T`.
+    * [`StreamError::MismatchedValues`] if the values of a sample do not match the stream's format.
+    */
+    pub fn data<T: XdfScalar>(&self) -> Result<Vec<&[T]>, StreamError> {
+        if self.format != T::FORMAT {
+            return Err(StreamError::WrongFormat {
+                requested: T::FORMAT,
+                actual: self.format,
+            });
+        }
+
+        self.samples
+            .iter()
+            .map(|s| {
+                s.values
+                    .as_slice::<T>()
+                    .ok_or(StreamError::MismatchedValues(self.format))
+            })
+            .collect()
+    }
+
+    /**
+    Returns the values of all samples as a matrix of `f64`, with one row per sample.
+    The conversion is lossless, all formats except `Int64` fit into an `f64` exactly.
+
+    # Errors
+    * [`StreamError::UnsupportedFormat`] for string streams and `Int64` streams.
+    * [`StreamError::MismatchedValues`] if the values of a sample do not match the stream's format.
+    */
+    pub fn as_f64(&self) -> Result<Vec<Vec<f64>>, StreamError> {
+        fn widen<T: XdfScalar + Into<f64>>(stream: &Stream) -> Result<Vec<Vec<f64>>, StreamError> {
+            Ok(stream
+                .data::<T>()?
+                .into_iter()
+                .map(|row| row.iter().map(|&v| v.into()).collect())
+                .collect())
+        }
+
+        match self.format {
+            Format::Int8 => widen::<i8>(self),
+            Format::Int16 => widen::<i16>(self),
+            Format::Int32 => widen::<i32>(self),
+            Format::Float32 => widen::<f32>(self),
+            Format::Float64 => widen::<f64>(self),
+            Format::Int64 | Format::String => Err(StreamError::UnsupportedFormat(self.format)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int_stream() -> Stream {
+        let samples = (0..3).map(|i| (f64::from(i), Values::Int32(vec![i, -i])));
+        Stream::from_test_timestamps(Format::Int32, 2, Some(1.0), samples)
+    }
+
+    #[test]
+    fn test_data() {
+        let stream = int_stream();
+
+        let data = stream.data::<i32>().unwrap();
+        assert_eq!(data, vec![&[0, 0][..], &[1, -1], &[2, -2]]);
+
+        assert!(matches!(
+            stream.data::<f32>(),
+            Err(StreamError::WrongFormat {
+                requested: Format::Float32,
+                actual: Format::Int32
+            })
+        ));
+    }
+
+    #[test]
+    fn test_as_f64() {
+        let stream = int_stream();
+        assert_eq!(
+            stream.as_f64().unwrap(),
+            vec![vec![0.0, 0.0], vec![1.0, -1.0], vec![2.0, -2.0]]
+        );
+
+        let int64_stream = Stream::from_test_samples(Format::Int64, 1, None, Vec::new());
+        assert!(matches!(
+            int64_stream.as_f64(),
+            Err(StreamError::UnsupportedFormat(Format::Int64))
+        ));
+    }
+
+    #[test]
+    fn test_mismatched_values() {
+        let mut stream = int_stream();
+        stream.samples[1].values = Values::Int8(vec![1, 2]);
+
+        assert!(matches!(
+            stream.data::<i32>(),
+            Err(StreamError::MismatchedValues(Format::Int32))
+        ));
+    }
+
+    #[test]
+    fn test_scalar_formats() {
+        assert_eq!(Values::Float32(vec![1.0]).as_slice::<f32>(), Some(&[1.0_f32][..]));
+        assert_eq!(Values::Float32(vec![1.0]).as_slice::<f64>(), None);
+        assert_eq!(i8::into_values(vec![1]).format(), i8::FORMAT);
+        assert_eq!(f64::into_values(vec![1.0]).format(), f64::FORMAT);
+    }
+}
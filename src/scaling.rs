@@ -0,0 +1,240 @@
+use crate::{errors::StreamError, util::set_text_of_child, ChannelInfo, Format, Sample, Stream, Values};
+
+// the names of the channel fields which hold the factor from raw counts to physical units, in order of preference
+const GAIN_FIELDS: [&str; 4] = ["scaling_factor", "scale", "gain", "resolution"];
+// the names of the channel fields which hold an offset added after scaling
+const OFFSET_FIELDS: [&str; 2] = ["scaling_offset", "offset"];
+
+/// The linear conversion of a channel's raw values into physical units: `physical = raw * gain + offset`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scaling {
+    /// The factor the raw values are multiplied with, for example the resolution of an ADC in microvolts per count.
+    pub gain: f64,
+    /// The value added after multiplying with `gain`.
+    pub offset: f64,
+}
+
+impl Scaling {
+    /// The scaling which leaves the values unchanged.
+    pub const IDENTITY: Self = Self { gain: 1.0, offset: 0.0 };
+
+    /// A scaling by `gain` without an offset.
+    #[must_use]
+    pub const fn gain(gain: f64) -> Self {
+        Self { gain, offset: 0.0 }
+    }
+}
+
+impl Default for Scaling {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ChannelInfo {
+    /**
+    Returns the channel's scaling from its `desc` entry, if there is one.
+
+    There is no standard for this, so the first number found in `scaling_factor`, `scale`, `gain` or `resolution`
+    is used as the gain, and `scaling_offset` or `offset` as the offset (zero if neither is given).
+    */
+    #[must_use]
+    pub fn scaling(&self) -> Option<Scaling> {
+        let number = |fields: &[&str]| {
+            fields
+                .iter()
+                .find_map(|&field| self.extra.get(field)?.trim().parse::<f64>().ok())
+        };
+
+        Some(Scaling {
+            gain: number(&GAIN_FIELDS)?,
+            offset: number(&OFFSET_FIELDS).unwrap_or(0.0),
+        })
+    }
+}
+
+impl Stream {
+    /**
+    Converts the raw counts of an integer stream into physical values, using the scaling given for each channel
+    in the header's `desc`. Channels without scaling metadata are left unscaled. See [`ChannelInfo::scaling`].
+
+    # Returns
+    A new [`Stream`] with [`Format::Float64`] values. Its header is updated accordingly.
+
+    # Errors
+    * [`StreamError::UnsupportedFormat`] for streams which are not of an integer format.
+    * [`StreamError::MismatchedValues`] if the values of a sample do not match the stream's format or channel count.
+    */
+    pub fn to_physical(&self) -> Result<Self, StreamError> {
+        let scalings: Vec<Scaling> = (0..self.channel_count as usize)
+            .map(|i| self.channels.get(i).and_then(ChannelInfo::scaling).unwrap_or_default())
+            .collect();
+
+        self.to_physical_with(&scalings)
+    }
+
+    /**
+    Converts the raw counts of an integer stream into physical values using one [`Scaling`] per channel,
+    for example from the amplifier's documentation. See [`Stream::to_physical`].
+
+    # Errors
+    * [`StreamError::UnsupportedFormat`] for streams which are not of an integer format.
+    * [`StreamError::WrongChannelCount`] if the number of scalings does not match the channel count.
+    * [`StreamError::MismatchedValues`] if the values of a sample do not match the stream's format or channel count.
+    */
+    pub fn to_physical_with(&self, scalings: &[Scaling]) -> Result<Self, StreamError> {
+        if !matches!(
+            self.format,
+            Format::Int8 | Format::Int16 | Format::Int32 | Format::Int64
+        ) {
+            return Err(StreamError::UnsupportedFormat(self.format));
+        }
+        if scalings.len() != self.channel_count as usize {
+            return Err(StreamError::WrongChannelCount {
+                expected: self.channel_count,
+                actual: scalings.len(),
+            });
+        }
+
+        let samples = self
+            .samples
+            .iter()
+            .map(|sample| {
+                let values = sample
+                    .values
+                    .to_f64_vec()
+                    .filter(|values| sample.values.format() == self.format && values.len() == scalings.len())
+                    .ok_or(StreamError::MismatchedValues(self.format))?;
+
+                Ok(Sample {
+                    timestamp: sample.timestamp,
+                    values: Values::Float64(
+                        values
+                            .iter()
+                            .zip(scalings)
+                            .map(|(value, scaling)| value * scaling.gain + scaling.offset)
+                            .collect(),
+                    ),
+                })
+            })
+            .collect::<Result<_, StreamError>>()?;
+
+        let mut header = self.header.clone();
+        set_text_of_child(&mut header, "channel_format", Format::Float64.as_xdf_str());
+
+        Ok(Self {
+            format: Format::Float64,
+            header,
+            samples,
+            recorded_timestamps: self.recorded_timestamps.clone(),
+            ..self.clone_without_samples()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::parse_channels;
+
+    const EPSILON: f64 = 1E-9;
+
+    fn raw_stream() -> Stream {
+        let samples = (0..4).map(|i| (f64::from(i), Values::Int16(vec![i * 100, -i])));
+        let mut stream = Stream::from_test_timestamps(Format::Int16, 2, Some(1.0), samples);
+        stream.header = xmltree::Element::parse(
+            r"<info>
+                <channel_format>int16</channel_format>
+                <desc>
+                    <channels>
+                        <channel><label>Cz</label><unit>microvolts</unit><resolution>0.1</resolution></channel>
+                        <channel><label>Trigger</label></channel>
+                    </channels>
+                </desc>
+            </info>"
+                .as_bytes(),
+        )
+        .unwrap();
+        stream.channels = parse_channels(&stream.header);
+        stream
+    }
+
+    #[test]
+    fn test_channel_scaling() {
+        let stream = raw_stream();
+        assert_eq!(stream.channels[0].scaling(), Some(Scaling::gain(0.1)));
+        assert_eq!(stream.channels[1].scaling(), None);
+
+        let mut channel = ChannelInfo::default();
+        channel.extra.insert("scaling_factor".to_string(), " 2.5 ".to_string());
+        channel.extra.insert("offset".to_string(), "-1".to_string());
+        assert_eq!(
+            channel.scaling(),
+            Some(Scaling {
+                gain: 2.5,
+                offset: -1.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_physical() {
+        let physical = raw_stream().to_physical().unwrap();
+
+        assert_eq!(physical.format, Format::Float64);
+        assert_eq!(
+            physical.header.get_child("channel_format").unwrap().get_text().unwrap(),
+            "double64"
+        );
+
+        let Values::Float64(values) = &physical.samples[3].values else {
+            panic!("expected float64 values");
+        };
+        assert!((values[0] - 30.0).abs() < EPSILON);
+        assert!((values[1] + 3.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_to_physical_with() {
+        let stream = raw_stream();
+
+        let physical = stream
+            .to_physical_with(&[
+                Scaling::gain(0.5),
+                Scaling {
+                    gain: 1.0,
+                    offset: 10.0,
+                },
+            ])
+            .unwrap();
+        assert_eq!(physical.samples[2].values, Values::Float64(vec![100.0, 8.0]));
+
+        assert!(matches!(
+            stream.to_physical_with(&[Scaling::IDENTITY]),
+            Err(StreamError::WrongChannelCount { expected: 2, actual: 1 })
+        ));
+
+        let physical = stream.to_physical().unwrap();
+        assert!(matches!(
+            physical.to_physical(),
+            Err(StreamError::UnsupportedFormat(Format::Float64))
+        ));
+    }
+
+    #[test]
+    fn test_to_physical_mismatched_values() {
+        let mut stream = raw_stream();
+        stream.samples[1].values = Values::Int16(vec![1]);
+        assert!(matches!(
+            stream.to_physical(),
+            Err(StreamError::MismatchedValues(Format::Int16))
+        ));
+
+        stream.samples[1].values = Values::Int32(vec![1, 2]);
+        assert!(matches!(
+            stream.to_physical_with(&[Scaling::IDENTITY; 2]),
+            Err(StreamError::MismatchedValues(Format::Int16))
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{srate_between, streams::Stream};
+
+/// A contiguous run of samples in a regularly sampled stream, i.e. a stretch of the recording without any gaps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    /// Index of the first sample of the segment in [`Stream::samples`].
+    pub start_index: usize,
+    /// Index of the last sample of the segment in [`Stream::samples`] (inclusive).
+    pub end_index: usize,
+    /// Timestamp of the first sample of the segment.
+    pub start_time: f64,
+    /// Timestamp of the last sample of the segment.
+    pub end_time: f64,
+    /// The sampling rate measured within this segment, in Hertz.
+    /// This is defined like [`Stream::measured_srate`], so for a stream without gaps the two are equal.
+    /// This is None if the segment consists of a single sample or its timestamps do not increase.
+    pub effective_srate: Option<f64>,
+}
+
+impl Segment {
+    /// The number of samples in the segment.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.end_index - self.start_index + 1
+    }
+
+    /// Always false, a segment contains at least one sample. Only here to keep clippy happy.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        false
+    }
+
+    fn new(start_index: usize, end_index: usize, start_time: f64, end_time: f64) -> Self {
+        Self {
+            start_index,
+            end_index,
+            start_time,
+            end_time,
+            effective_srate: srate_between(end_index - start_index + 1, start_time, end_time),
+        }
+    }
+}
+
+impl Stream {
+    /**
+    Splits a regularly sampled stream into segments wherever the time between two consecutive samples
+    is larger than `max_gap` nominal sampling periods.
+    This finds breaks in the recording such as dropped network packets or a paused device,
+    which are hidden by the single [`Stream::measured_srate`] figure.
+
+    # Arguments
+    * `max_gap` - The largest tolerated distance between two samples, in multiples of the nominal sampling period.
+      Values around 1.5 to 2 are a reasonable starting point.
+
+    # Returns
+    A vector of segments in the order of the samples.
+    It is empty if the stream has no nominal sampling rate (for example marker streams), or no timestamped samples.
+    Samples without a timestamp are skipped.
+    */
+    #[must_use]
+    pub fn segments(&self, max_gap: f64) -> Vec<Segment> {
+        let Some(nominal_srate) = self.nominal_srate else {
+            return Vec::new();
+        };
+        let max_delta = max_gap / nominal_srate;
+
+        let mut timestamps = self
+            .samples
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.timestamp.map(|ts| (i, ts)));
+
+        let Some((mut start_index, mut start_time)) = timestamps.next() else {
+            return Vec::new();
+        };
+        let (mut prev_index, mut prev_time) = (start_index, start_time);
+
+        let mut segments = Vec::new();
+        for (i, ts) in timestamps {
+            if ts - prev_time > max_delta {
+                segments.push(Segment::new(start_index, prev_index, start_time, prev_time));
+                (start_index, start_time) = (i, ts);
+            }
+            (prev_index, prev_time) = (i, ts);
+        }
+        segments.push(Segment::new(start_index, prev_index, start_time, prev_time));
+
+        segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Format, Values};
+
+    const EPSILON: f64 = 1E-9;
+
+    fn stream_with_timestamps(nominal_srate: Option<f64>, timestamps: &[f64]) -> Stream {
+        let samples = timestamps.iter().map(|&ts| (ts, Values::Float32(vec![0.0])));
+        Stream::from_test_timestamps(Format::Float32, 1, nominal_srate, samples)
+    }
+
+    #[test]
+    fn test_no_gaps() {
+        let timestamps: Vec<f64> = (0..10).map(|i| f64::from(i) / 10.0).collect();
+        let stream = stream_with_timestamps(Some(10.0), &timestamps);
+
+        let segments = stream.segments(1.5);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].start_index, 0);
+        assert_eq!(segments[0].end_index, 9);
+        assert_eq!(segments[0].len(), 10);
+        assert!((segments[0].effective_srate.unwrap() - 10.0).abs() < EPSILON);
+
+        // without gaps the segment's rate is the stream's measured rate
+        assert_eq!(
+            crate::measured_srate(&stream.samples, stream.nominal_srate),
+            segments[0].effective_srate
+        );
+    }
+
+    #[test]
+    fn test_gaps() {
+        // a gap of 5 periods after the third sample and a gap of 2 periods after the sixth
+        let timestamps = [0.0, 0.1, 0.2, 0.7, 0.8, 0.9, 1.1, 1.2];
+        let stream = stream_with_timestamps(Some(10.0), &timestamps);
+
+        let segments = stream.segments(1.5);
+        let bounds: Vec<(usize, usize)> = segments.iter().map(|s| (s.start_index, s.end_index)).collect();
+        assert_eq!(bounds, vec![(0, 2), (3, 5), (6, 7)]);
+        assert!((segments[1].start_time - 0.7).abs() < EPSILON);
+        assert!((segments[1].end_time - 0.9).abs() < EPSILON);
+
+        // a more lenient threshold only splits at the larger gap
+        assert_eq!(stream.segments(3.0).len(), 2);
+    }
+
+    #[test]
+    fn test_single_sample_segment() {
+        let stream = stream_with_timestamps(Some(10.0), &[0.0, 1.0]);
+
+        let segments = stream.segments(1.5);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[1].len(), 1);
+        assert_eq!(segments[1].effective_srate, None);
+    }
+
+    #[test]
+    fn test_irregular_stream() {
+        let stream = stream_with_timestamps(None, &[0.0, 1.0, 5.0]);
+        assert!(stream.segments(1.5).is_empty());
+    }
+}
@@ -0,0 +1,335 @@
+use std::ops::Range;
+
+use xmltree::{Element, XMLNode};
+
+use crate::{
+    channels::parse_channels, errors::StreamError, measured_srate, util::set_text_of_child, Sample, Stream, Values,
+};
+
+/// A channel of a stream, either by its index or by its label. Used by [`Stream::pick_channels`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelSelector<'a> {
+    /// The index of the channel, i.e. its position in the values of each sample.
+    Index(usize),
+    /// The label of the channel as given in the header's `desc/channels`, see [`crate::ChannelInfo::label`].
+    Label(&'a str),
+}
+
+impl From<usize> for ChannelSelector<'_> {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl<'a> From<&'a str> for ChannelSelector<'a> {
+    fn from(label: &'a str) -> Self {
+        Self::Label(label)
+    }
+}
+
+impl Stream {
+    /**
+    Returns a copy of the stream containing only the samples at `range` in [`Stream::samples`].
+    The range is clamped to the available samples.
+
+    The measured sampling rate and the recorded timestamps are updated to the new samples.
+    The footer is dropped since it describes the whole recording.
+    */
+    #[must_use]
+    pub fn slice_samples(&self, range: Range<usize>) -> Self {
+        let end = range.end.min(self.samples.len());
+        let start = range.start.min(end);
+
+        let mut sliced = self.clone_without_samples();
+        sliced.samples = self.samples[start..end].to_vec();
+        sliced.recorded_timestamps = self
+            .recorded_timestamps
+            .iter()
+            .filter(|&&i| (start..end).contains(&i))
+            .map(|i| i - start)
+            .collect();
+        sliced.measured_srate = measured_srate(&sliced.samples, sliced.nominal_srate);
+        sliced.footer = None;
+        sliced.footer_info = None;
+
+        sliced
+    }
+
+    /**
+    Returns a copy of the stream containing only the samples with timestamps from `start` (inclusive)
+    to `end` (exclusive), so that consecutive slices do not overlap. See [`Stream::slice_samples`].
+    Samples without a timestamp are kept if they lie between two samples in the window.
+
+    # Errors
+    [`StreamError::InvalidWindow`] if `start` is after `end` or either is not finite.
+    */
+    pub fn slice_time(&self, start: f64, end: f64) -> Result<Self, StreamError> {
+        if !(start.is_finite() && end.is_finite()) || start > end {
+            return Err(StreamError::InvalidWindow { start, end });
+        }
+
+        // from the first to the last sample with a timestamp in the window, untimed samples between them included
+        let first = self.timestamped_from(self.partition_by(|ts| ts < start));
+        let last = self.timestamped_before(self.partition_by(|ts| ts < end));
+
+        let range = match (first, last) {
+            (Some(first), Some(last)) if first <= last => first..last + 1,
+            _ => 0..0,
+        };
+
+        Ok(self.slice_samples(range))
+    }
+
+    /**
+    Returns a copy of the stream with only the given channels, in the given order.
+    The channel count in the header is updated and the header's `desc/channels` and [`Stream::channels`]
+    are reduced to the picked channels.
+
+    # Example
+    ```rust
+    # use std::fs;
+    # use xdf::XDFFile;
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = fs::read("tests/minimal.xdf")?;
+    let xdf_file = XDFFile::from_bytes(&bytes)?;
+
+    let stream = xdf_file.stream_by_id(0)?;
+    let picked = stream.pick_channels(&[2_usize, 0])?;
+    assert_eq!(picked.channel_count, 2);
+    # Ok(())
+    # }
+    ```
+
+    # Errors
+    [`StreamError::ChannelNotFound`] if an index is out of range or no channel has a given label.
+    If several channels have the same label, the first one is picked.
+    */
+    pub fn pick_channels<'a, C>(&self, channels: &[C]) -> Result<Self, StreamError>
+    where
+        C: Copy + Into<ChannelSelector<'a>>,
+    {
+        let indices = channels
+            .iter()
+            .map(|&channel| self.channel_index(channel.into()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut picked = self.clone_without_samples();
+        picked.samples = self
+            .samples
+            .iter()
+            .map(|sample| Sample {
+                timestamp: sample.timestamp,
+                values: sample.values.pick(&indices),
+            })
+            .collect();
+        picked.recorded_timestamps.clone_from(&self.recorded_timestamps);
+
+        // indices are validated against the channel count, which is a u32
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            picked.channel_count = indices.len() as u32;
+        }
+        set_text_of_child(&mut picked.header, "channel_count", &picked.channel_count.to_string());
+        prune_desc_channels(&mut picked.header, &indices);
+        picked.channels = parse_channels(&picked.header);
+
+        Ok(picked)
+    }
+
+    fn channel_index(&self, channel: ChannelSelector) -> Result<usize, StreamError> {
+        match channel {
+            ChannelSelector::Index(index) if index < self.channel_count as usize => Ok(index),
+            ChannelSelector::Index(index) => Err(StreamError::ChannelNotFound(format!("index {index}"))),
+            ChannelSelector::Label(label) => self
+                .channels
+                .iter()
+                .position(|c| c.label.as_deref() == Some(label))
+                .filter(|&index| index < self.channel_count as usize)
+                .ok_or_else(|| StreamError::ChannelNotFound(format!("label {label:?}"))),
+        }
+    }
+}
+
+impl Values {
+    // the values at the given indices, skipping indices which are out of range
+    fn pick(&self, indices: &[usize]) -> Self {
+        fn pick<T: Clone>(values: &[T], indices: &[usize]) -> Vec<T> {
+            indices.iter().filter_map(|&i| values.get(i).cloned()).collect()
+        }
+
+        match self {
+            Self::Int8(v) => Self::Int8(pick(v, indices)),
+            Self::Int16(v) => Self::Int16(pick(v, indices)),
+            Self::Int32(v) => Self::Int32(pick(v, indices)),
+            Self::Int64(v) => Self::Int64(pick(v, indices)),
+            Self::Float32(v) => Self::Float32(pick(v, indices)),
+            Self::Float64(v) => Self::Float64(pick(v, indices)),
+            Self::String(v) => Self::String(pick(v, indices)),
+        }
+    }
+}
+
+// keeps only the picked <channel> elements of desc/channels, in the order they were picked.
+// Picked channels without an element get an empty one so that the elements stay aligned with the values.
+fn prune_desc_channels(header: &mut Element, indices: &[usize]) {
+    let Some(channels) = header
+        .get_mut_child("desc")
+        .and_then(|desc| desc.get_mut_child("channels"))
+    else {
+        return;
+    };
+
+    let elements: Vec<&Element> = channels
+        .children
+        .iter()
+        .filter_map(|node| node.as_element())
+        .filter(|element| element.name == "channel")
+        .collect();
+
+    let picked = indices
+        .iter()
+        .map(|&i| {
+            XMLNode::Element(
+                elements
+                    .get(i)
+                    .map_or_else(|| Element::new("channel"), |&element| element.clone()),
+            )
+        })
+        .collect();
+
+    channels.children = picked;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Format;
+
+    // 10 Hz from 0 to 0.9 s with three channels labelled by the header
+    fn stream() -> Stream {
+        let samples = (0..10).map(|i| (f64::from(i) / 10.0, Values::Int16(vec![i, 10 + i, 20 + i])));
+        let mut stream = Stream::from_test_timestamps(Format::Int16, 3, Some(10.0), samples);
+        stream.header = Element::parse(
+            r"<info>
+                <channel_count>3</channel_count>
+                <desc>
+                    <channels>
+                        <channel><label>Fz</label></channel>
+                        <channel><label>Cz</label></channel>
+                        <channel><label>Pz</label></channel>
+                    </channels>
+                </desc>
+            </info>"
+                .as_bytes(),
+        )
+        .unwrap();
+        stream.channels = parse_channels(&stream.header);
+        stream
+    }
+
+    #[test]
+    fn test_slice_samples() {
+        let mut stream = stream();
+        stream.recorded_timestamps = vec![0, 5, 9];
+
+        let sliced = stream.slice_samples(4..20);
+
+        assert_eq!(sliced.samples.len(), 6);
+        assert_eq!(sliced.samples[0], stream.samples[4]);
+        assert_eq!(sliced.recorded_timestamps, vec![1, 5]);
+        assert!(sliced.footer_info.is_none());
+
+        assert!(stream.slice_samples(12..15).samples.is_empty());
+    }
+
+    #[test]
+    fn test_slice_time() {
+        let stream = stream();
+
+        let first = stream.slice_time(0.0, 0.5).unwrap();
+        let second = stream.slice_time(0.5, 10.0).unwrap();
+        assert_eq!(first.samples.len(), 5);
+        assert_eq!(second.samples.len(), 5);
+        assert_eq!(second.samples[0].timestamp, Some(0.5));
+
+        assert!(matches!(
+            stream.slice_time(0.5, 0.0),
+            Err(StreamError::InvalidWindow { .. })
+        ));
+    }
+
+    #[test]
+    fn test_slice_time_missing_timestamps() {
+        let mut stream = stream();
+        for i in [0, 3, 4, 9] {
+            stream.samples[i].timestamp = None;
+        }
+
+        // the samples without a timestamp between 0.2 and 0.5 are kept, the ones at the edges are not
+        let sliced = stream.slice_time(0.15, 0.55).unwrap();
+        assert_eq!(sliced.samples, stream.samples[2..6]);
+
+        assert_eq!(stream.slice_time(0.0, 10.0).unwrap().samples, stream.samples[1..9]);
+        assert!(stream.slice_time(0.31, 0.39).unwrap().samples.is_empty());
+
+        // an untimed sample must not hide the earlier samples from the search
+        let mut markers = stream.slice_samples(0..3);
+        markers.samples[0].timestamp = Some(5.0);
+        markers.samples[1].timestamp = None;
+        markers.samples[2].timestamp = Some(6.0);
+        assert_eq!(markers.slice_time(1.0, 10.0).unwrap().samples.len(), 3);
+    }
+
+    #[test]
+    fn test_pick_channels() {
+        let stream = stream();
+
+        let picked = stream.pick_channels(&["Pz", "Fz"]).unwrap();
+        assert_eq!(picked.channel_count, 2);
+        assert_eq!(picked.samples[3].values, Values::Int16(vec![23, 3]));
+        assert_eq!(
+            picked.header.get_child("channel_count").unwrap().get_text().unwrap(),
+            "2"
+        );
+
+        let labels: Vec<_> = picked.channels.iter().map(|c| c.label.as_deref().unwrap()).collect();
+        assert_eq!(labels, vec!["Pz", "Fz"]);
+
+        let by_index = stream.pick_channels(&[2_usize, 0]).unwrap();
+        assert_eq!(by_index, picked);
+    }
+
+    #[test]
+    fn test_pick_channels_partial_desc() {
+        // the header only describes the first two of the three channels
+        let mut stream = stream();
+        let channels = stream
+            .header
+            .get_mut_child("desc")
+            .and_then(|desc| desc.get_mut_child("channels"))
+            .unwrap();
+        channels.children.pop();
+        stream.channels = parse_channels(&stream.header);
+        assert_eq!(stream.channels.len(), 2);
+
+        let picked = stream.pick_channels(&[2_usize, 0]).unwrap();
+        assert_eq!(picked.samples[3].values, Values::Int16(vec![23, 3]));
+
+        let labels: Vec<_> = picked.channels.iter().map(|c| c.label.as_deref()).collect();
+        assert_eq!(labels, vec![None, Some("Fz")]);
+    }
+
+    #[test]
+    fn test_pick_missing_channels() {
+        let stream = stream();
+
+        assert!(matches!(
+            stream.pick_channels(&[3_usize]),
+            Err(StreamError::ChannelNotFound(_))
+        ));
+        assert!(matches!(
+            stream.pick_channels(&["Oz"]),
+            Err(StreamError::ChannelNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use xmltree::Element;
+
+use crate::util::{get_text_from_child, parse_child};
+
+/**
+The standard fields of a stream header which LSL writes in addition to the name, type, channel count,
+sampling rate and format (those are fields of [`crate::Stream`] itself).
+
+All fields are optional. Fields which are present but cannot be parsed are None and a warning is logged.
+Non-standard fields are still available through [`crate::Stream::header`].
+*/
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamInfo {
+    /// A unique identifier of this stream, generated by LSL.
+    pub uid: Option<String>,
+    /// An identifier of the data source, given by the application which created the stream.
+    /// Used to reconnect to the same source after interruptions.
+    pub source_id: Option<String>,
+    /// The time at which the stream was created, in the clock of the machine which created it.
+    pub created_at: Option<f64>,
+    /// The session the stream belongs to.
+    pub session_id: Option<String>,
+    /// The name of the machine which created the stream.
+    pub hostname: Option<String>,
+    /// The version of the LSL protocol used by the stream, for example `1.1`.
+    pub version: Option<f64>,
+    /// The IPv4 address the stream was served on.
+    pub v4address: Option<Ipv4Addr>,
+    /// The IPv4 port the data was served on.
+    pub v4data_port: Option<u16>,
+    /// The IPv4 port the stream's service was offered on.
+    pub v4service_port: Option<u16>,
+    /// The IPv6 address the stream was served on.
+    pub v6address: Option<Ipv6Addr>,
+    /// The IPv6 port the data was served on.
+    pub v6data_port: Option<u16>,
+    /// The IPv6 port the stream's service was offered on.
+    pub v6service_port: Option<u16>,
+}
+
+impl StreamInfo {
+    pub(crate) fn from_header(header: &Element) -> Self {
+        let text = |name: &str| {
+            get_text_from_child(header, name)
+                .ok()
+                .map(|text| text.trim().to_string())
+                .filter(|text| !text.is_empty())
+        };
+
+        Self {
+            uid: text("uid"),
+            source_id: text("source_id"),
+            created_at: parse_child(header, "created_at"),
+            session_id: text("session_id"),
+            hostname: text("hostname"),
+            version: parse_child(header, "version"),
+            v4address: parse_child(header, "v4address"),
+            v4data_port: parse_child(header, "v4data_port"),
+            v4service_port: parse_child(header, "v4service_port"),
+            v6address: parse_child(header, "v6address"),
+            v6data_port: parse_child(header, "v6data_port"),
+            v6service_port: parse_child(header, "v6service_port"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_info() {
+        let header = Element::parse(
+            r"<info>
+                <name>EEG</name>
+                <uid>5a1b-42</uid>
+                <source_id>amp_1</source_id>
+                <created_at>50942.5</created_at>
+                <session_id>default</session_id>
+                <hostname>lab-pc</hostname>
+                <version>1.1000000000000001</version>
+                <v4address>192.168.0.2</v4address>
+                <v4data_port>16573</v4data_port>
+                <v4service_port>not a port</v4service_port>
+                <v6address/>
+                <v6data_port>99999</v6data_port>
+            </info>"
+                .as_bytes(),
+        )
+        .unwrap();
+
+        let info = StreamInfo::from_header(&header);
+
+        assert_eq!(info.uid.as_deref(), Some("5a1b-42"));
+        assert_eq!(info.source_id.as_deref(), Some("amp_1"));
+        assert_eq!(info.created_at, Some(50_942.5));
+        assert_eq!(info.session_id.as_deref(), Some("default"));
+        assert_eq!(info.hostname.as_deref(), Some("lab-pc"));
+        assert_eq!(info.version, Some(1.1));
+        assert_eq!(info.v4address, Some(Ipv4Addr::new(192, 168, 0, 2)));
+        assert_eq!(info.v4data_port, Some(16573));
+        // invalid and empty values are ignored
+        assert_eq!(info.v4service_port, None);
+        assert_eq!(info.v6address, None);
+        assert_eq!(info.v6data_port, None);
+        assert_eq!(info.v6service_port, None);
+    }
+
+    #[test]
+    fn test_stream_info_empty() {
+        let header = Element::parse("<info/>".as_bytes()).unwrap();
+        assert_eq!(StreamInfo::from_header(&header), StreamInfo::default());
+    }
+}
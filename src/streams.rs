@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::{Format, Sample};
+use crate::{ChannelInfo, ClockModel, ClockOffset, Format, Sample, StreamFooter, StreamInfo};
 
 // minimal tags in version 1.0:
 // channel count
@@ -12,21 +12,145 @@ use crate::{Format, Sample};
 // type
 // desc
 
+/// A single stream in an XDF file, for example an EEG recording or a marker stream.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Stream {
-    pub id: u32, // not really necessary but nie for debugging and testing
+    /// The stream ID as given in the file. Not really necessary but nice for debugging and testing.
+    pub id: u32,
+    /// The number of channels, i.e. the number of values in each sample.
     pub channel_count: u32,
-    pub nominal_srate: Option<f64>, //a mandatory field but we replace zero with None
+    /// The nominal sampling rate in Hertz. A mandatory field but we replace zero (irregular sampling rate) with None.
+    pub nominal_srate: Option<f64>,
+    /// The format of the values in this stream.
     pub format: Format,
 
     // optional fields:
+    /// The name of the stream, if given.
     pub name: Option<Arc<str>>,
+    /// The type of the stream, if given, for example `EEG` or `Markers`.
     pub r#type: Option<Arc<str>>,
 
-    pub header: xmltree::Element, //contains desc
+    /// The standard fields of the header which are not covered above, such as the uid and hostname.
+    pub info: StreamInfo,
+
+    /// The XML header of the stream. It contains `desc` which holds further, non-standardised information.
+    #[cfg_attr(feature = "serde", serde(with = "crate::xml_serde"))]
+    pub header: xmltree::Element,
+    /// The XML footer of the stream, if there is one.
+    #[cfg_attr(feature = "serde", serde(with = "crate::xml_serde::option"))]
     pub footer: Option<xmltree::Element>,
+    /// The contents of [`Stream::footer`], if there is one. See [`Stream::verify_footer`] to check them against the samples.
+    pub footer_info: Option<StreamFooter>,
+    /// The metadata of each channel from the header's `desc/channels`. Empty if the header does not describe its channels.
+    pub channels: Vec<ChannelInfo>,
 
+    /// The sampling rate as measured from the timestamps of the first and last sample,
+    /// i.e. the number of sampling periods between them divided by the time span.
+    /// None if the stream has no nominal sampling rate.
     pub measured_srate: Option<f64>,
 
+    /// The samples of the stream in order.
     pub samples: Vec<Sample>,
+
+    /// The indices of the samples in [`Stream::samples`] whose timestamps were read from the file, in ascending order.
+    /// The timestamps of all other samples were calculated using the nominal sampling rate.
+    /// Note that clock offsets are applied to all timestamps alike.
+    pub recorded_timestamps: Vec<usize>,
+
+    /// The clock offsets recorded for this stream, in order of collection time.
+    pub clock_offsets: Vec<ClockOffset>,
+    /// The clock synchronisation model which was applied to the timestamps, based on [`Stream::clock_offsets`].
+    pub clock_model: ClockModel,
+
+    /// The time in the clock of the recording computer which the timestamps of this stream are relative to.
+    /// This is zero unless the stream was rebased, see [`Stream::set_time_origin`].
+    pub time_origin: f64,
+}
+
+impl Stream {
+    // clones everything except the samples and their recorded timestamps, which are usually replaced anyway
+    pub(crate) fn clone_without_samples(&self) -> Self {
+        Self {
+            id: self.id,
+            channel_count: self.channel_count,
+            nominal_srate: self.nominal_srate,
+            format: self.format,
+            name: self.name.clone(),
+            r#type: self.r#type.clone(),
+            info: self.info.clone(),
+            header: self.header.clone(),
+            footer: self.footer.clone(),
+            footer_info: self.footer_info.clone(),
+            channels: self.channels.clone(),
+            measured_srate: self.measured_srate,
+            samples: Vec::new(),
+            recorded_timestamps: Vec::new(),
+            clock_offsets: self.clock_offsets.clone(),
+            clock_model: self.clock_model.clone(),
+            time_origin: self.time_origin,
+        }
+    }
+
+    /// Returns true if the timestamp of the sample at `index` was read from the file,
+    /// and false if it was calculated from the nominal sampling rate or the sample has no timestamp.
+    #[must_use]
+    pub fn is_timestamp_recorded(&self, index: usize) -> bool {
+        self.recorded_timestamps.binary_search(&index).is_ok()
+    }
+}
+
+#[cfg(test)]
+impl Stream {
+    // builds a stream with an otherwise empty header around the given samples. Only meant for unit tests.
+    pub(crate) fn from_test_samples(
+        format: Format,
+        channel_count: u32,
+        nominal_srate: Option<f64>,
+        samples: Vec<Sample>,
+    ) -> Self {
+        let recorded_timestamps = samples
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.timestamp.map(|_| i))
+            .collect();
+
+        Self {
+            id: 0,
+            channel_count,
+            nominal_srate,
+            format,
+            name: None,
+            r#type: None,
+            info: StreamInfo::default(),
+            header: xmltree::Element::new("info"),
+            footer: None,
+            footer_info: None,
+            channels: Vec::new(),
+            measured_srate: None,
+            samples,
+            recorded_timestamps,
+            clock_offsets: Vec::new(),
+            clock_model: ClockModel::None,
+            time_origin: 0.0,
+        }
+    }
+
+    // builds a stream from pairs of timestamp and values, where the timestamp is an f64 or an Option<f64>
+    pub(crate) fn from_test_timestamps<T: Into<Option<f64>>>(
+        format: Format,
+        channel_count: u32,
+        nominal_srate: Option<f64>,
+        samples: impl IntoIterator<Item = (T, crate::Values)>,
+    ) -> Self {
+        let samples = samples
+            .into_iter()
+            .map(|(timestamp, values)| Sample {
+                timestamp: timestamp.into(),
+                values,
+            })
+            .collect();
+
+        Self::from_test_samples(format, channel_count, nominal_srate, samples)
+    }
 }
@@ -0,0 +1,113 @@
+use crate::{Stream, XDFFile};
+
+/// The origin of the timestamps of all streams after parsing, chosen through [`crate::ParseOptions::time_origin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeOrigin {
+    /// Keep the timestamps in the clock of the recording computer, which is usually the LSL clock.
+    #[default]
+    Recorder,
+    /// Rebase all streams to seconds since the earliest sample in the file, so that this sample is at time zero.
+    FirstSample,
+}
+
+impl Stream {
+    /**
+    Shifts the timestamps of this stream so that they are relative to `origin`, given in the clock of the recording computer.
+    [`Stream::time_origin`] is updated accordingly, so calling this repeatedly does not accumulate.
+
+    Note that [`Stream::clock_offsets`] and [`Stream::clock_model`] always remain in the stream's original clock.
+    */
+    pub fn set_time_origin(&mut self, origin: f64) {
+        let shift = self.time_origin - origin;
+
+        for timestamp in self.samples.iter_mut().filter_map(|s| s.timestamp.as_mut()) {
+            *timestamp += shift;
+        }
+
+        self.time_origin = origin;
+    }
+}
+
+impl XDFFile {
+    /// Returns the earliest timestamp of any sample in the file in the clock of the recording computer,
+    /// or None if there are no timestamped samples.
+    #[must_use]
+    pub fn first_timestamp(&self) -> Option<f64> {
+        self.streams
+            .iter()
+            .filter_map(|stream| {
+                stream
+                    .samples
+                    .iter()
+                    .filter_map(|s| s.timestamp)
+                    .reduce(f64::min)
+                    .map(|ts| ts + stream.time_origin)
+            })
+            .reduce(f64::min)
+    }
+
+    /**
+    Shifts the timestamps of all streams so that they are relative to `origin`, given in the clock of the recording computer.
+    This puts all streams on a common time base, see [`Stream::set_time_origin`].
+    */
+    pub fn set_time_origin(&mut self, origin: f64) {
+        for stream in &mut self.streams {
+            stream.set_time_origin(origin);
+        }
+
+        self.time_origin = origin;
+    }
+
+    /// Rebases all streams to seconds since the earliest sample in the file. Does nothing if there are no timestamped samples.
+    pub fn rebase_to_first_sample(&mut self) {
+        if let Some(origin) = self.first_timestamp() {
+            self.set_time_origin(origin);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Format, Values};
+
+    const EPSILON: f64 = 1E-12;
+
+    fn stream_with_timestamps(timestamps: &[f64]) -> Stream {
+        let samples = timestamps.iter().map(|&ts| (ts, Values::Float64(vec![0.0])));
+        Stream::from_test_timestamps(Format::Float64, 1, Some(10.0), samples)
+    }
+
+    #[test]
+    fn test_set_time_origin() {
+        let mut stream = stream_with_timestamps(&[10.0, 10.1, 10.2]);
+
+        stream.set_time_origin(10.0);
+        assert!((stream.samples[1].timestamp.unwrap() - 0.1).abs() < EPSILON);
+
+        // going back to the recorder's clock restores the original timestamps
+        stream.set_time_origin(0.0);
+        assert!((stream.samples[1].timestamp.unwrap() - 10.1).abs() < EPSILON);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_rebase_to_first_sample() {
+        let mut xdf_file = XDFFile::from_test_streams(vec![
+            stream_with_timestamps(&[12.0, 13.0]),
+            stream_with_timestamps(&[11.5, 14.0]),
+        ]);
+
+        assert_eq!(xdf_file.first_timestamp(), Some(11.5));
+
+        xdf_file.rebase_to_first_sample();
+        assert_eq!(xdf_file.time_origin, 11.5);
+        assert_eq!(xdf_file.streams[0].time_origin, 11.5);
+        assert!((xdf_file.streams[0].samples[0].timestamp.unwrap() - 0.5).abs() < EPSILON);
+        assert!(xdf_file.streams[1].samples[0].timestamp.unwrap().abs() < EPSILON);
+
+        // the first timestamp is always given in the recorder's clock
+        assert_eq!(xdf_file.first_timestamp(), Some(11.5));
+    }
+}
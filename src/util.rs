@@ -1,4 +1,7 @@
-use xmltree::Element;
+use std::str::FromStr;
+
+use log::warn;
+use xmltree::{Element, XMLNode};
 
 use crate::errors::{ParseError, XDFError, XMLError};
 
@@ -25,6 +28,32 @@ pub(crate) fn get_text_from_child(root: &Element, child_name: &str) -> Result<St
         .to_string())
 }
 
+// sets the text of the child with the given name, creating the child if it does not exist yet
+pub(crate) fn set_text_of_child(root: &mut Element, child_name: &str, text: &str) {
+    if root.get_child(child_name).is_none() {
+        root.children.push(XMLNode::Element(Element::new(child_name)));
+    }
+
+    if let Some(child) = root.get_mut_child(child_name) {
+        child.children = vec![XMLNode::Text(text.to_string())];
+    }
+}
+
+// parses the text of a child element, None if it is missing or empty. Invalid values are logged.
+pub(crate) fn parse_child<T: FromStr>(root: &Element, name: &str) -> Option<T> {
+    let text = get_text_from_child(root, name).ok()?;
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let value = text.parse::<T>().ok();
+    if value.is_none() {
+        warn!("Ignoring invalid value {text:?} of {name} in {}", root.name);
+    }
+    value
+}
+
 // #[derive(Debug, Error)]
 // pub(crate) struct NotFiniteError();
 // impl Display for NotFiniteError {
@@ -130,3 +159,16 @@ fn test_get_text_from_child_empty_child() {
     let result = get_text_from_child(&root, "child");
     assert!(result.is_err());
 }
+
+#[test]
+fn test_set_text_of_child() {
+    let mut root = Element::new("root");
+
+    set_text_of_child(&mut root, "child", "first");
+    assert_eq!(get_text_from_child(&root, "child").unwrap(), "first");
+
+    // existing children are overwritten rather than duplicated
+    set_text_of_child(&mut root, "child", "second");
+    assert_eq!(get_text_from_child(&root, "child").unwrap(), "second");
+    assert_eq!(root.children.len(), 1);
+}
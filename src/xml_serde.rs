@@ -0,0 +1,40 @@
+//! Serialises XML elements as strings, for use with `#[serde(with = "...")]`. Only available with the `serde` feature.
+
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serializer};
+use xmltree::{Element, EmitterConfig};
+
+fn to_string(element: &Element) -> Result<String, xmltree::Error> {
+    let mut bytes = Vec::new();
+    element.write_with_config(&mut bytes, EmitterConfig::new().write_document_declaration(false))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+pub(crate) fn serialize<S: Serializer>(element: &Element, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&to_string(element).map_err(S::Error::custom)?)
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Element, D::Error> {
+    let xml = String::deserialize(deserializer)?;
+    Element::parse(xml.as_bytes()).map_err(D::Error::custom)
+}
+
+pub(crate) mod option {
+    use super::{Deserialize, Deserializer, Element, Serializer};
+
+    #[allow(clippy::ref_option)] // the signature is dictated by serde
+    pub(crate) fn serialize<S: Serializer>(element: &Option<Element>, serializer: S) -> Result<S::Ok, S::Error> {
+        match element {
+            Some(element) => {
+                let xml = super::to_string(element).map_err(serde::ser::Error::custom)?;
+                serializer.serialize_some(&xml)
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Element>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|xml| Element::parse(xml.as_bytes()).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
@@ -1,6 +1,9 @@
-use std::fs;
+use std::{fs, sync::Arc};
 
-use xdf::{Format, Values, XDFFile};
+use xdf::{
+    ClockModel, ClockOffset, FileInfo, Format, LeastSquaresSync, NoSync, ParseOptions, StreamOrder, TimeOrigin,
+    UnknownChunk, Values, XDFFile,
+};
 
 const EPSILON: f64 = 1E-15;
 
@@ -11,16 +14,13 @@ fn read_minimal_xdf() {
     let bytes = fs::read(file_path).unwrap();
     let xdf_file = XDFFile::from_bytes(&bytes).unwrap();
 
-    //must be sorted
-    let stream_ids: [u32; 2] = [0, 0x02C0_FFEE];
+    // in the order of the stream headers in the file
+    let expected_ids: [u32; 2] = [0, 0x02C0_FFEE];
 
     assert_eq!(xdf_file.header.name, "info");
 
-    assert_eq!(xdf_file.streams.len(), stream_ids.len());
-    let mut read_ids = xdf_file.streams.iter().map(|stream| stream.id).collect::<Vec<u32>>();
-    let mut expected_ids = stream_ids;
-    read_ids.sort_unstable();
-    expected_ids.sort_unstable();
+    assert_eq!(xdf_file.streams.len(), expected_ids.len());
+    let read_ids = xdf_file.streams.iter().map(|stream| stream.id).collect::<Vec<u32>>();
     assert_eq!(read_ids, expected_ids);
 
     let first_stream = xdf_file.streams.iter().find(|s| s.id == expected_ids[0]).unwrap();
@@ -163,9 +163,11 @@ fn read_minimal_xdf() {
     // check strings
     for (&expected, actual_sample) in expected_second_samples.iter().zip(second_stream.samples.iter()) {
         match actual_sample.values {
-            Values::String(ref s) => {
+            Values::String(ref strings) => {
+                assert_eq!(strings.len(), 1, "expected a single channel in second stream");
+
                 // remove all whitespace
-                let mut actual_string = s.to_owned();
+                let mut actual_string = strings[0].clone();
                 actual_string.retain(|c| !c.is_whitespace());
                 let mut expected = expected.to_string();
                 expected.retain(|c| !c.is_whitespace());
@@ -230,3 +232,202 @@ fn out_of_order_sample_chunk() {
 
     let _xdf_file = XDFFile::from_bytes(&bytes);
 }
+
+#[test]
+fn recorded_timestamps_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+    let xdf_file = XDFFile::from_bytes(&bytes).unwrap();
+
+    // both streams in this file were written in chunks whose first samples carry a timestamp
+    for stream in &xdf_file.streams {
+        assert_eq!(stream.recorded_timestamps, vec![0, 1, 4, 5]);
+        assert!(stream.is_timestamp_recorded(4));
+        assert!(!stream.is_timestamp_recorded(2));
+    }
+}
+
+#[test]
+fn clock_offsets_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+    let xdf_file = XDFFile::from_bytes(&bytes).unwrap();
+
+    let expected_offsets = vec![
+        ClockOffset {
+            collection_time: 6.1,
+            offset_value: -0.1,
+        },
+        ClockOffset {
+            collection_time: 7.1,
+            offset_value: -0.1,
+        },
+    ];
+
+    let first_stream = xdf_file.streams.iter().find(|s| s.id == 0).unwrap();
+    assert_eq!(first_stream.clock_offsets, expected_offsets);
+    assert_eq!(first_stream.clock_model, ClockModel::PiecewiseLinear(expected_offsets));
+
+    // the string stream has no clock offsets
+    let second_stream = xdf_file.streams.iter().find(|s| s.id == 0x02C0_FFEE).unwrap();
+    assert!(second_stream.clock_offsets.is_empty());
+    assert_eq!(second_stream.clock_model, ClockModel::None);
+}
+
+#[test]
+fn clock_sync_options_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+
+    let options = ParseOptions {
+        clock_sync: Arc::new(NoSync),
+        ..Default::default()
+    };
+    let xdf_file = XDFFile::from_bytes_with_options(&bytes, &options).unwrap();
+    let stream = xdf_file.streams.iter().find(|s| s.id == 0).unwrap();
+
+    // without clock synchronisation the timestamps are exactly as recorded
+    assert_eq!(stream.clock_model, ClockModel::None);
+    assert!((stream.samples[0].timestamp.unwrap() - 5.1).abs() < EPSILON);
+
+    // a constant offset is fitted exactly by least squares
+    let options = ParseOptions {
+        clock_sync: Arc::new(LeastSquaresSync),
+        ..Default::default()
+    };
+    let xdf_file = XDFFile::from_bytes_with_options(&bytes, &options).unwrap();
+    let stream = xdf_file.streams.iter().find(|s| s.id == 0).unwrap();
+
+    for (i, sample) in stream.samples.iter().enumerate() {
+        let expected = 5.1 + 0.1 * i as f64 - 0.1;
+        assert!((sample.timestamp.unwrap() - expected).abs() < 1E-12);
+    }
+}
+
+#[test]
+fn time_origin_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+
+    let options = ParseOptions {
+        time_origin: TimeOrigin::FirstSample,
+        ..Default::default()
+    };
+    let xdf_file = XDFFile::from_bytes_with_options(&bytes, &options).unwrap();
+
+    // the earliest sample is the first sample of the first stream at 5.1 - 0.1
+    assert!((xdf_file.time_origin - 5.0).abs() < EPSILON);
+
+    let first_stream = xdf_file.streams.iter().find(|s| s.id == 0).unwrap();
+    assert!((first_stream.time_origin - 5.0).abs() < EPSILON);
+    assert!(first_stream.samples[0].timestamp.unwrap().abs() < EPSILON);
+    assert!((first_stream.samples[8].timestamp.unwrap() - 0.8).abs() < 1E-12);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_roundtrip_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+    let xdf_file = XDFFile::from_bytes(&bytes).unwrap();
+
+    let json = serde_json::to_string(&xdf_file).unwrap();
+    let deserialized: XDFFile = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(deserialized, xdf_file);
+}
+
+#[test]
+fn channels_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+    let xdf_file = XDFFile::from_bytes(&bytes).unwrap();
+
+    // the headers of minimal.xdf have an empty desc
+    assert!(xdf_file.streams.iter().all(|s| s.channels.is_empty()));
+}
+
+#[test]
+fn stream_info_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+    let xdf_file = XDFFile::from_bytes(&bytes).unwrap();
+
+    let stream = xdf_file.streams.iter().find(|s| s.id == 0).unwrap();
+    assert_eq!(stream.info.uid.as_deref(), Some("xdfwriter_11_int"));
+    assert!((stream.info.created_at.unwrap() - 50_942.723_319_709).abs() < EPSILON);
+    assert_eq!(stream.info.hostname, None);
+}
+
+#[test]
+fn file_info_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+    let xdf_file = XDFFile::from_bytes(&bytes).unwrap();
+
+    // the minimal file's header only holds the version
+    assert_eq!(xdf_file.info, FileInfo::default());
+}
+
+#[test]
+fn unknown_chunk_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+    let expected = XDFFile::from_bytes(&bytes).unwrap();
+    assert!(expected.unknown_chunks.is_empty());
+
+    // a chunk with tag 42 between the file header and the stream headers must not stop the parse
+    let mut with_unknown = bytes.clone();
+    with_unknown.splice(64..64, [1, 4, 42, 0, 0xAB, 0xCD]);
+    let xdf_file = XDFFile::from_bytes(&with_unknown).unwrap();
+
+    assert_eq!(xdf_file.streams, expected.streams);
+    assert_eq!(
+        xdf_file.unknown_chunks,
+        vec![UnknownChunk {
+            tag: 42,
+            offset: 64,
+            bytes: vec![0xAB, 0xCD],
+        }]
+    );
+}
+
+#[test]
+fn footer_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+    let xdf_file = XDFFile::from_bytes(&bytes).unwrap();
+
+    for stream in &xdf_file.streams {
+        let footer = stream.footer_info.as_ref().unwrap();
+        assert_eq!(footer.sample_count, Some(9));
+        assert_eq!(footer.first_timestamp, Some(5.1));
+        assert_eq!(footer.last_timestamp, Some(5.9));
+        assert_eq!(footer.clock_offsets.len(), 2);
+
+        assert!(stream.verify_footer(0.05).is_empty());
+    }
+}
+
+#[test]
+fn stream_queries_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+    let xdf_file = XDFFile::from_bytes(&bytes).unwrap();
+
+    assert_eq!(xdf_file.stream_by_name("SendDataC").unwrap().id, 0);
+    assert_eq!(xdf_file.stream_by_type("StringMarker").unwrap().id, 0x02C0_FFEE);
+    assert_eq!(xdf_file.stream_by_uid("xdfwriter_11_int").unwrap().id, 0);
+    assert_eq!(xdf_file.stream_by_id(0x02C0_FFEE).unwrap().format, Format::String);
+    assert!(xdf_file.stream_by_name("missing").is_err());
+    assert_eq!(xdf_file.streams_where(|s| s.nominal_srate == Some(10.0)).len(), 2);
+}
+
+#[test]
+fn stream_order_minimal() {
+    let bytes = fs::read("tests/minimal.xdf").unwrap();
+    let ids = |stream_order| {
+        let options = ParseOptions {
+            stream_order,
+            ..Default::default()
+        };
+        let xdf_file = XDFFile::from_bytes_with_options(&bytes, &options).unwrap();
+        xdf_file.streams.iter().map(|s| s.id).collect::<Vec<_>>()
+    };
+
+    // parsing twice gives the same order
+    assert_eq!(ids(StreamOrder::File), ids(StreamOrder::File));
+    assert_eq!(ids(StreamOrder::File), vec![0, 0x02C0_FFEE]);
+    assert_eq!(ids(StreamOrder::Id), vec![0, 0x02C0_FFEE]);
+    // "SendDataC" < "SendDataString"
+    assert_eq!(ids(StreamOrder::Name), vec![0, 0x02C0_FFEE]);
+}